@@ -0,0 +1,73 @@
+//! Times the string and the column engine of day 3 on generated reports.
+//!
+//! ```Shell
+//! cargo bench --bench diagnostic -- <lines> <width>
+//! ```
+
+use advent_of_code::days::diagnostic::{criteria_filter, rates, TiePolicy, CO2_SCRUB, O2_GEN};
+use advent_of_code::days::diagnostic_packed::ColumnReport;
+use std::time::{Duration, Instant};
+
+/// `count` lines of `width` bits from a linear congruential generator.
+fn generate(count: usize, width: usize) -> Vec<String> {
+    let mut state = 2021u64;
+    (0..count)
+        .map(|_| {
+            (0..width)
+                .map(|bit| {
+                    if bit % 32 == 0 {
+                        state = state
+                            .wrapping_mul(6364136223846793005)
+                            .wrapping_add(1442695040888963407);
+                    }
+                    if state >> (32 + bit % 32) & 1 == 1 {
+                        '1'
+                    } else {
+                        '0'
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn time<T>(label: &str, run: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = run();
+    let elapsed = start.elapsed();
+    println!("{:<24}{:>10.1?}", label, elapsed);
+    (result, elapsed)
+}
+
+fn main() {
+    // Cargo passes --bench along to benchmarks
+    let numbers: Vec<usize> = std::env::args()
+        .skip(1)
+        .filter_map(|arg| arg.parse().ok())
+        .collect();
+    let count = numbers.first().copied().unwrap_or(1 << 20);
+    let width = numbers.get(1).copied().unwrap_or(64);
+    let lines = generate(count, width);
+    let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+    let policy = TiePolicy::One;
+    println!("{} lines of {} bits", count, width);
+
+    let (string_rates, strings) = time("strings: rates", || rates(&lines, policy));
+    let (string_ratings, filtered) = time("strings: ratings", || {
+        [O2_GEN, CO2_SCRUB].map(|criteria| criteria_filter(lines.clone(), criteria, policy))
+    });
+
+    let (report, packing) = time("columns: packing", || ColumnReport::new(&lines).unwrap());
+    let (column_rates, counted) = time("columns: rates", || report.rates(policy));
+    let (column_ratings, rated) = time("columns: ratings", || {
+        [O2_GEN, CO2_SCRUB].map(|criteria| report.rating(criteria, policy))
+    });
+
+    assert_eq!(string_rates, column_rates);
+    assert_eq!(string_ratings, column_ratings);
+    println!(
+        "speedup: {:.1}x, {:.1}x without packing",
+        (strings + filtered).as_secs_f64() / (packing + counted + rated).as_secs_f64(),
+        (strings + filtered).as_secs_f64() / (counted + rated).as_secs_f64()
+    );
+}
@@ -0,0 +1,18 @@
+fn main() {
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    // Only the items of the ffi module make up the C API, not every public constant of the crate
+    let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+    let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+        .expect("Unable to read cbindgen.toml");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(format!("{}/src/ffi.rs", crate_dir))
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(format!(
+            "{}/advent_of_code.h",
+            std::env::var("OUT_DIR").unwrap()
+        ));
+}
@@ -0,0 +1,56 @@
+#ifndef ADVENT_OF_CODE_H
+#define ADVENT_OF_CODE_H
+
+/* Generated by cbindgen from src/ffi.rs, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Status codes returned by `aoc_solve`.
+ */
+typedef enum AocStatus {
+  AOC_STATUS_OK = 0,
+  AOC_STATUS_UNKNOWN_PUZZLE = 1,
+  AOC_STATUS_INVALID_INPUT = 2,
+  AOC_STATUS_INVALID_UTF8 = 3,
+  AOC_STATUS_BUFFER_TOO_SMALL = 4,
+  AOC_STATUS_NULL_POINTER = 5,
+  AOC_STATUS_INVALID_PARAMETER = 6,
+} AocStatus;
+
+/**
+ * Caller owned buffer receiving the NUL terminated answer, or the error message on failure.
+ * `length` is always set to the length the full text needs (without the NUL), so a call that
+ * returned `AOC_STATUS_BUFFER_TOO_SMALL` can be repeated with a bigger buffer.
+ */
+typedef struct AocBuffer {
+  char *data;
+  size_t capacity;
+  size_t length;
+} AocBuffer;
+
+/**
+ * Solves one part of a puzzle for the input of `len` bytes at `input_ptr`.
+ *
+ * # Safety
+ *
+ * `input_ptr` must point to `len` readable bytes and `out_buf` to an `AocBuffer` whose `data`
+ * points to `capacity` writable bytes.
+ */
+enum AocStatus aoc_solve(uint16_t year,
+                         uint8_t day,
+                         uint8_t part,
+                         const uint8_t *input_ptr,
+                         size_t len,
+                         struct AocBuffer *out_buf);
+
+/**
+ * Returns a static, NUL terminated description of a status code.
+ */
+const char *aoc_status_message(int status);
+
+#endif  /* ADVENT_OF_CODE_H */
@@ -0,0 +1,257 @@
+use std::fmt::{Binary, Display, Formatter};
+use std::ops::{Add, Mul};
+use std::str::FromStr;
+
+/// A word of any width, doubling as an unsigned integer of any size. Day 3 keeps its rates and
+/// ratings in words, day 6 the number of lanternfish.
+///
+/// Bits are indexed like the characters of a report line, the most significant first. The bits
+/// are kept in 64 bit limbs, the least significant limb first.
+#[derive(Clone, Debug, Default)]
+pub struct Word {
+    limbs: Vec<u64>,
+    width: usize,
+}
+
+impl Word {
+    pub fn zero(width: usize) -> Self {
+        Self {
+            limbs: vec![0; width.div_ceil(64)],
+            width,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The limb and the mask within it of bit `index`, counting from the most significant.
+    fn locate(&self, index: usize) -> (usize, u64) {
+        assert!(
+            index < self.width,
+            "Bit {} of a {} bit word",
+            index,
+            self.width
+        );
+        let position = self.width - 1 - index;
+        (position / 64, 1 << (position % 64))
+    }
+
+    pub fn bit(&self, index: usize) -> bool {
+        let (limb, mask) = self.locate(index);
+        self.limbs[limb] & mask != 0
+    }
+
+    pub fn set(&mut self, index: usize) {
+        let (limb, mask) = self.locate(index);
+        self.limbs[limb] |= mask;
+    }
+
+    /// Every bit of the word flipped, as epsilon is of gamma.
+    pub fn complement(&self) -> Self {
+        let mut limbs: Vec<u64> = self.limbs.iter().map(|limb| !limb).collect();
+        if let Some(last) = limbs.last_mut() {
+            if !self.width.is_multiple_of(64) {
+                *last &= (1 << (self.width % 64)) - 1;
+            }
+        }
+        Self {
+            limbs,
+            width: self.width,
+        }
+    }
+
+    /// The limbs without the zeros leading the number.
+    fn significant(&self) -> &[u64] {
+        let length = self
+            .limbs
+            .iter()
+            .rposition(|&limb| limb != 0)
+            .map_or(0, |last| last + 1);
+        &self.limbs[..length]
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        match self.significant() {
+            [] => Some(0),
+            [low] => Some(*low as u128),
+            [low, high] => Some((*high as u128) << 64 | *low as u128),
+            _ => None,
+        }
+    }
+
+    /// Divides the number by `divisor` in place, returning the remainder.
+    fn divide(limbs: &mut [u64], divisor: u64) -> u64 {
+        let mut remainder = 0u128;
+        for limb in limbs.iter_mut().rev() {
+            let value = remainder << 64 | *limb as u128;
+            *limb = (value / divisor as u128) as u64;
+            remainder = value % divisor as u128;
+        }
+        remainder as u64
+    }
+}
+
+impl From<u64> for Word {
+    fn from(value: u64) -> Self {
+        Self {
+            limbs: vec![value],
+            width: 64,
+        }
+    }
+}
+
+impl FromStr for Word {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mut word = Word::zero(string.len());
+        for (index, character) in string.chars().enumerate() {
+            match character {
+                '0' => {}
+                '1' => word.set(index),
+                _ => return Err("Not a binary word!"),
+            }
+        }
+        Ok(word)
+    }
+}
+
+/// Words are equal when their numbers are, whatever their widths.
+impl PartialEq for Word {
+    fn eq(&self, other: &Self) -> bool {
+        self.significant() == other.significant()
+    }
+}
+
+impl Eq for Word {}
+
+impl Mul for &Word {
+    type Output = Word;
+
+    fn mul(self, other: &Word) -> Word {
+        let mut limbs = vec![0u64; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let value = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = value as u64;
+                carry = value >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        Word {
+            limbs,
+            width: self.width + other.width,
+        }
+    }
+}
+
+impl Mul for Word {
+    type Output = Word;
+
+    fn mul(self, other: Word) -> Word {
+        &self * &other
+    }
+}
+
+/// As wide as the wider of the two, or the sum if that needs more bits.
+impl Add for &Word {
+    type Output = Word;
+
+    fn add(self, other: &Word) -> Word {
+        let length = self.limbs.len().max(other.limbs.len()) + 1;
+        let mut limbs = vec![0u64; length];
+        let mut carry = 0u128;
+        for (index, limb) in limbs.iter_mut().enumerate() {
+            let value = carry
+                + self.limbs.get(index).copied().unwrap_or(0) as u128
+                + other.limbs.get(index).copied().unwrap_or(0) as u128;
+            *limb = value as u64;
+            carry = value >> 64;
+        }
+        let significant = limbs.iter().rposition(|&limb| limb != 0).map_or(0, |last| {
+            (last + 1) * 64 - limbs[last].leading_zeros() as usize
+        });
+        let width = self.width.max(other.width).max(significant);
+        limbs.truncate(width.div_ceil(64));
+        Word { limbs, width }
+    }
+}
+
+impl Add for Word {
+    type Output = Word;
+
+    fn add(self, other: Word) -> Word {
+        &self + &other
+    }
+}
+
+/// The number in decimal.
+impl Display for Word {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut limbs = self.significant().to_vec();
+        let mut chunks = Vec::new();
+        while limbs.iter().any(|&limb| limb != 0) {
+            chunks.push(Word::divide(&mut limbs, CHUNK));
+        }
+        match chunks.split_last() {
+            None => write!(f, "0"),
+            Some((first, rest)) => {
+                write!(f, "{}", first)?;
+                rest.iter()
+                    .rev()
+                    .try_for_each(|chunk| write!(f, "{:019}", chunk))
+            }
+        }
+    }
+}
+
+/// The bit string, as wide as the word.
+impl Binary for Word {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (0..self.width).try_for_each(|index| write!(f, "{}", if self.bit(index) { 1 } else { 0 }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::Word;
+
+    #[test]
+    fn test_word() {
+        let word: Word = "10110".parse().unwrap();
+        assert_eq!(Word::from(22), word);
+        assert!(word.bit(0) && !word.bit(1));
+        assert_eq!(Some(9), word.complement().to_u128());
+        assert_eq!("01001", format!("{:b}", word.complement()));
+        assert!("10120".parse::<Word>().is_err());
+
+        let wide: Word = "1".repeat(100).parse().unwrap();
+        assert_eq!(100, wide.width());
+        assert_eq!(Some((1 << 100) - 1), wide.to_u128());
+        assert_eq!(None, (&wide * &wide).to_u128());
+        assert_eq!(Word::from(0), wide.complement());
+        assert_eq!("1267650600228229401496703205375", wide.to_string());
+        // (2^100 - 1)^2 = 2^200 - 2^101 + 1
+        assert_eq!(
+            "1606938044258990275541962092338627301321746534979799428890625",
+            (&wide * &wide).to_string()
+        );
+        assert_eq!("0", Word::zero(70).to_string());
+        assert_eq!(
+            "10000000000000000000",
+            (Word::from(10_000_000_000) * Word::from(1_000_000_000)).to_string()
+        );
+
+        let sum = &wide + &Word::from(1);
+        assert_eq!(101, sum.width());
+        assert_eq!(Some(1 << 100), sum.to_u128());
+        assert_eq!(100, (&wide + &Word::zero(3)).width());
+        assert_eq!(
+            Some(1 << 64),
+            (Word::from(u64::MAX) + Word::from(1)).to_u128()
+        );
+    }
+}
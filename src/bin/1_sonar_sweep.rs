@@ -1,40 +1,134 @@
-use advent_of_code::read_input_ints;
-
-fn sonar_sweep(input: Vec<u32>) -> usize {
-    input
-        .as_slice()
-        .windows(2)
-        .filter(|window| window[1] > window[0])
-        .count()
+use advent_of_code::days::sonar_analytics::Analysis;
+use advent_of_code::days::sonar_chart::{braille_chart, sparkline, svg, HEIGHT, WIDTH};
+use advent_of_code::days::sonar_sweep::{read_depths, Depth, DepthError, SonarSweep, PARAMETERS};
+use advent_of_code::input_path;
+use advent_of_code::params::Parameters;
+use advent_of_code::theme::Theme;
+use std::fs::File;
+use std::io::{stdin, BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: 1_sonar_sweep [--input <file>|-] [--depths u32|i64|f64]
+                     [--param window=<depths>]... [--config <file>]
+                     [--analyze [--rolling <depths>] [--sigmas <deviations>]]
+                     [--chart] [--svg <file>]";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1)
 }
 
-fn filtered_sonar_sweep(input: Vec<u32>) -> usize {
-    sonar_sweep(
-        input
-            .as_slice()
-            .windows(3)
-            .map(|window| window.iter().sum())
-            .collect(),
-    )
+struct Options {
+    parts: [Parameters; 2],
+    analyze: bool,
+    chart: bool,
+    image: Option<String>,
+    rolling: usize,
+    sigmas: f64,
 }
 
 fn main() {
-    println!("{:?}", filtered_sonar_sweep(read_input_ints!()));
-}
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (parts, rest) =
+        Parameters::from_args(&PARAMETERS, 1, &args).unwrap_or_else(|message| fail(&message));
 
-#[cfg(test)]
-mod tests {
-    use crate::{filtered_sonar_sweep, sonar_sweep};
+    let mut path = None;
+    let mut depths = "u32".to_string();
+    let mut options = Options {
+        parts,
+        analyze: false,
+        chart: false,
+        image: None,
+        rolling: 10,
+        sigmas: 3.0,
+    };
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--analyze" => {
+                options.analyze = true;
+                continue;
+            }
+            "--chart" => {
+                options.chart = true;
+                continue;
+            }
+            _ => {}
+        }
+        match (arg.as_str(), rest.next()) {
+            ("--input", Some(value)) => path = Some(value.clone()),
+            ("--depths", Some(value)) => depths = value.clone(),
+            ("--svg", Some(value)) => options.image = Some(value.clone()),
+            ("--rolling", Some(value)) => {
+                options.rolling = value
+                    .parse()
+                    .ok()
+                    .filter(|&rolling| rolling > 0)
+                    .unwrap_or_else(|| fail("--rolling needs a positive number of depths"))
+            }
+            ("--sigmas", Some(value)) => {
+                options.sigmas = value
+                    .parse()
+                    .unwrap_or_else(|_| fail("--sigmas needs a number of deviations"))
+            }
+            _ => fail(USAGE),
+        }
+    }
 
-    const TEST_SET: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+    // The depths are streamed, so logs of any size can be piped in with `--input -`
+    let reader: Box<dyn BufRead> = match path.as_deref() {
+        Some("-") => Box::new(stdin().lock()),
+        path => {
+            let path = path.map_or_else(|| input_path!(), PathBuf::from);
+            let file = File::open(&path).unwrap_or_else(|error| {
+                fail(&format!("Cannot read {}: {}", path.display(), error))
+            });
+            Box::new(BufReader::new(file))
+        }
+    };
+    match depths.as_str() {
+        "u32" => survey(read_depths::<u32, _>(reader), &options),
+        "i64" => survey(read_depths::<i64, _>(reader), &options),
+        "f64" => survey(read_depths::<f64, _>(reader), &options),
+        _ => fail("--depths should be u32, i64 or f64"),
+    }
+}
 
-    #[test]
-    fn test_sonar_sweep() {
-        assert_eq!(7, sonar_sweep(TEST_SET.to_vec()));
+fn survey<D: Depth>(readings: impl Iterator<Item = Result<D, DepthError>>, options: &Options) {
+    let mut sweeps = options
+        .parts
+        .each_ref()
+        .map(|parameters| SonarSweep::new(parameters.integer("window") as usize));
+    // Only the analysis and the charts need all depths at once
+    let keep = options.analyze || options.chart || options.image.is_some();
+    let mut depths = Vec::new();
+    for depth in readings {
+        let depth = depth.unwrap_or_else(|error| fail(&error.to_string()));
+        sweeps.iter_mut().for_each(|sweep| sweep.push(depth));
+        if keep {
+            depths.push(depth);
+        }
+    }
+    for (sweep, parameters) in sweeps.iter().zip(&options.parts) {
+        println!("{:?} ({})", sweep.increases(), parameters);
+    }
+    if options.analyze {
+        println!(
+            "{}",
+            Analysis::new(&depths, options.rolling, options.sigmas)
+        );
     }
 
-    #[test]
-    fn test_filtered_sonar_sweep() {
-        assert_eq!(5, filtered_sonar_sweep(TEST_SET.to_vec()));
+    let window = options.parts[1].integer("window") as usize;
+    if options.chart {
+        let theme = Theme::current();
+        println!("{}", sparkline(&depths, WIDTH, theme));
+        println!("{}", braille_chart(&depths, window, WIDTH, HEIGHT, theme));
+    }
+    if let Some(image) = &options.image {
+        let chart = svg(&depths, window, 1000, 400, &Theme::current().palette);
+        std::fs::write(image, chart).unwrap_or_else(|error| fail(&error.to_string()));
     }
 }
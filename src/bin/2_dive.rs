@@ -1,113 +1,150 @@
-use advent_of_code::read_input_lines;
-use std::str::FromStr;
-
-#[derive(Copy, Clone)]
-enum Direction {
-    Forward(usize),
-    Up(usize),
-    Down(usize),
+use advent_of_code::days::dive::{self, trace, BreachPolicy, Direction, PARAMETERS};
+use advent_of_code::days::dive_chart::{plot, svg, HEIGHT, WIDTH};
+use advent_of_code::days::dive_planner::{plan, Target};
+use advent_of_code::days::dive_script::Script;
+use advent_of_code::params::Parameters;
+use advent_of_code::read_input;
+use advent_of_code::theme::Theme;
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage: 2_dive [--param surface=error|clamp|fly] [--param model=progress|aim|drag]...
+              [--config <file>] [--script <file>] [--plot] [--svg <file>]
+              [--plan <horizontal>,<depth> [--max-step <units>]]
+
+--script runs a navigation script instead of the input, --plot draws the course of both parts and
+--svg writes a side view of the course of part 2. --plan prints the shortest commands reaching a
+target instead, moving at most 9 units per command unless --max-step says otherwise";
+
+fn fail(message: &str) -> ! {
+    eprintln!("{}", message);
+    exit(1)
 }
 
-impl FromStr for Direction {
-    type Err = &'static str;
-
-    fn from_str(string: &str) -> Result<Self, Self::Err> {
-        let direction: Vec<&str> = string.split_whitespace().collect();
-        if direction.len() != 2 {
-            return Err("Not a direction;unit pair!");
-        }
-        let units = match direction[1].parse::<usize>() {
-            Ok(units) => units,
-            Err(_) => return Err("not an integer!"),
-        };
-        match direction[0] {
-            "forward" => Ok(Direction::Forward(units)),
-            "up" => Ok(Direction::Up(units)),
-            "down" => Ok(Direction::Down(units)),
-            _ => Err("Not one of: [forward, up, down]"),
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let (parts, rest) =
+        Parameters::from_args(&PARAMETERS, 2, &args).unwrap_or_else(|message| fail(&message));
+    let (mut plotted, mut image, mut script) = (false, None, None);
+    let (mut target, mut max_step) = (None, 9);
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match arg.as_str() {
+            "--plot" => plotted = true,
+            "--svg" => image = Some(rest.next().unwrap_or_else(|| fail(USAGE))),
+            "--script" => {
+                let path = rest.next().unwrap_or_else(|| fail(USAGE));
+                let source = std::fs::read_to_string(path)
+                    .unwrap_or_else(|error| fail(&format!("Cannot read {}: {}", path, error)));
+                script = Some(
+                    source
+                        .parse::<Script>()
+                        .unwrap_or_else(|error| fail(&format!("{}: {}", path, error))),
+                );
+            }
+            "--plan" => {
+                let value = rest.next().unwrap_or_else(|| fail(USAGE));
+                target = Some(
+                    value
+                        .parse::<Target>()
+                        .unwrap_or_else(|reason| fail(&format!("--plan {}: {}", value, reason))),
+                );
+            }
+            "--max-step" => {
+                max_step = rest
+                    .next()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|&units| units > 0)
+                    .unwrap_or_else(|| fail("--max-step needs a positive number of units"))
+            }
+            _ => fail(USAGE),
         }
     }
-}
-
-struct Position {
-    pos: usize,
-    depth: usize,
-    aim: usize,
-}
-
-impl Position {
-    fn new(pos: usize, depth: usize, aim: usize) -> Self {
-        Position { pos, depth, aim }
-    }
-
-    fn progress(self, direction: Direction) -> Self {
-        match direction {
-            Direction::Forward(units) => Self::new(self.pos + units, self.depth, self.aim),
-            Direction::Up(units) => Self::new(self.pos, self.depth - units, self.aim),
-            Direction::Down(units) => Self::new(self.pos, self.depth + units, self.aim),
-        }
+    if let Some(target) = target {
+        route(&parts, target, max_step);
+        return;
     }
 
-    fn aim(self, direction: Direction) -> Self {
-        match direction {
-            Direction::Forward(units) => {
-                Self::new(self.pos + units, self.depth + self.aim * units, self.aim)
+    let input: Vec<Direction> = match script {
+        Some(_) => Vec::new(),
+        None => read_input!()
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                line.trim()
+                    .parse::<Direction>()
+                    .map_err(|reason| format!("Line {} ({}): {}", index + 1, line.trim(), reason))
+            })
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|message| fail(&message)),
+    };
+    let theme = Theme::current();
+    for (part, parameters) in (1..=2).zip(&parts) {
+        let label = format!("Part {}", part);
+        let model = dive::model(parameters.choice("model")).unwrap();
+        let policy = parameters
+            .choice("surface")
+            .parse::<BreachPolicy>()
+            .unwrap();
+        let course = match &script {
+            Some(script) => script
+                .run(model, policy)
+                .map(|course| (course.trajectory.clone(), course.to_string()))
+                .map_err(|error| error.to_string()),
+            None => trace(&input, model, policy)
+                .map(|trajectory| (trajectory.clone(), trajectory.to_string()))
+                .map_err(|error| error.to_string()),
+        };
+        let (trajectory, summary) = match course {
+            Ok(course) => course,
+            Err(error) => {
+                println!("{}: {}", label, error);
+                continue;
             }
-            Direction::Up(units) => Self::new(self.pos, self.depth, self.aim - units),
-            Direction::Down(units) => Self::new(self.pos, self.depth, self.aim + units),
+        };
+        match trajectory.end().product() {
+            Some(product) => println!("{}: {} ({})", label, product, parameters),
+            None => println!("{}: the product is out of range ({})", label, parameters),
+        }
+        println!("    {}", summary);
+        if plotted {
+            println!("{}", plot(&trajectory, WIDTH, HEIGHT, theme));
+        }
+        if let (Some(image), 2) = (image, part) {
+            let chart = svg(&trajectory, 1000, 400, &theme.palette);
+            std::fs::write(image, chart).unwrap_or_else(|error| fail(&error.to_string()));
         }
-    }
-
-    fn product(self) -> usize {
-        self.pos * self.depth
-    }
-}
-
-impl Default for Position {
-    fn default() -> Self {
-        Position::new(0, 0, 0)
     }
 }
 
-fn dive(input: &[Direction]) -> usize {
-    input
-        .iter()
-        .fold(Position::default(), |pos, direction| {
-            pos.progress(*direction)
-        })
-        .product()
-}
-
-fn aim(input: &[Direction]) -> usize {
-    input
-        .iter()
-        .fold(Position::default(), |pos, direction| pos.aim(*direction))
-        .product()
-}
-
-fn main() {
-    let input: Vec<Direction> = read_input_lines!()
-        .iter()
-        .flat_map(|line| line.parse::<Direction>())
-        .collect();
-    println!("Dive: {:?}", dive(&*input));
-    println!("Aim: {:?}", aim(&*input));
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::Direction::{Down, Forward, Up};
-    use crate::{aim, dive, Direction};
-
-    const TEST_SET: [Direction; 6] = [Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2)];
-
-    #[test]
-    fn test_dive() {
-        assert_eq!(150, dive(&TEST_SET.to_vec()));
-    }
-
-    #[test]
-    fn test_aim() {
-        assert_eq!(900, aim(&TEST_SET.to_vec()));
+/// Plans a route to `target` for the model of each part, and checks it gets there.
+fn route(parts: &[Parameters; 2], target: Target, max_step: usize) {
+    for (part, parameters) in (1..=2).zip(parts) {
+        let model = dive::model(parameters.choice("model")).unwrap();
+        let commands = match plan(model, target, max_step) {
+            Ok(commands) => commands,
+            Err(error) => {
+                println!("Part {}: {} ({})", part, error, parameters);
+                continue;
+            }
+        };
+        let end = trace(&commands, model, BreachPolicy::Error)
+            .expect("The plan should not breach the surface")
+            .end();
+        assert_eq!(
+            (target.pos as i64, target.depth as i64),
+            (end.pos, end.depth),
+            "The plan should reach the target"
+        );
+        println!(
+            "Part {}: {} commands ({})",
+            part,
+            commands.len(),
+            parameters
+        );
+        for direction in commands {
+            println!("{}", direction);
+        }
     }
 }
@@ -0,0 +1,29 @@
+mod repl;
+mod run;
+mod serve;
+mod sessions;
+
+use std::process::exit;
+
+const USAGE: &str = "\
+Usage:
+    aoc run <day> [part] [--input <file>] [--param <name=value>]... [--config <file>]
+    aoc serve [--host <address>] [--port <port>]
+    aoc repl <day> [--input <file>] [--param <name=value>]... [--config <file>]
+
+Parameters are read from the [day<N>] table of aoc.toml unless --config names another file.";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("run") => run::run(&args[1..]),
+        Some("serve") => serve::serve(&args[1..]),
+        Some("repl") => repl::repl(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(message) = result {
+        eprintln!("{}", message);
+        exit(1);
+    }
+}
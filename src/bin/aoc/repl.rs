@@ -0,0 +1,167 @@
+use crate::sessions::{session, Session};
+use advent_of_code::params::{Overrides, Parameters};
+use advent_of_code::solver::{panic_reason, parameters};
+use std::io::{stdin, stdout, BufRead, Write};
+use std::panic::{catch_unwind, set_hook, take_hook, AssertUnwindSafe};
+use std::path::PathBuf;
+use std::time::Instant;
+
+const HELP: &str = "\
+Commands:
+    run [part]            solve both parts, or only the given one
+    show                  list what can be inspected
+    show <topic> [args]   inspect the parsed input
+    params                list the parameters and their values per part
+    set <name> <value>    change a parameter, the next run picks it up
+    reset <name>          go back to the default of a parameter
+    help                  show this text
+    quit                  leave";
+
+pub fn default_input(day: u8) -> Result<PathBuf, String> {
+    let prefix = format!("{}_", day);
+    std::fs::read_dir("res")
+        .map_err(|error| format!("Cannot read res/: {}", error))?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with(&prefix) && name.ends_with(".txt"))
+                .unwrap_or(false)
+        })
+        .ok_or_else(|| format!("No input for day {} in res/", day))
+}
+
+pub fn repl(args: &[String]) -> Result<(), String> {
+    let usage =
+        || "Usage: aoc repl <day> [--input <file>] [--param <name=value>]... [--config <file>]";
+    let day = args.first().ok_or_else(usage)?;
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Not a day: {}", day))?;
+    let (mut overrides, rest) = Overrides::from_args(&args[1..], day)?;
+    Parameters::resolve(parameters(day), 1, &overrides)?;
+    let path = match rest.as_slice() {
+        [] => None,
+        [flag, path] if flag == "--input" => Some(PathBuf::from(path)),
+        _ => return Err(usage().to_string()),
+    };
+    let path = match path {
+        Some(path) => path,
+        None => default_input(day)?,
+    };
+    let input =
+        std::fs::read_to_string(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    let start = Instant::now();
+    let session = session(day, &input)?;
+    println!(
+        "Parsed {} in {:.3?}, type `help` for the commands",
+        path.display(),
+        start.elapsed()
+    );
+
+    let mut lines = stdin().lock().lines();
+    loop {
+        print!("aoc {}> ", day);
+        stdout().flush().unwrap();
+        let line = match lines.next() {
+            Some(Ok(line)) => line,
+            _ => break,
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            [] => {}
+            ["quit" | "exit"] => break,
+            ["help"] => println!("{}", HELP),
+            ["run"] => {
+                run(session.as_ref(), day, 1, &overrides);
+                run(session.as_ref(), day, 2, &overrides);
+            }
+            ["run", part] => match part.parse::<u8>() {
+                Ok(part) => run(session.as_ref(), day, part, &overrides),
+                Err(_) => println!("Not a part: {}", part),
+            },
+            ["show"] => {
+                for (topic, description) in session.topics() {
+                    println!("    {:<22}{}", topic, description);
+                }
+            }
+            ["show", topic, args @ ..] => {
+                let shown = Parameters::resolve(parameters(day), 1, &overrides)
+                    .and_then(|parameters| guarded(|| session.show(topic, args, &parameters)));
+                match shown {
+                    Ok(output) => println!("{}", output),
+                    Err(message) => println!("{}", message),
+                }
+            }
+            ["params"] => {
+                let declared = parameters(day);
+                if declared.is_empty() {
+                    println!("Day {} has no parameters", day);
+                }
+                let parts = [1, 2].map(|part| Parameters::resolve(declared, part, &overrides));
+                for (index, parameter) in declared.iter().enumerate() {
+                    let values: Vec<String> = parts
+                        .iter()
+                        .flatten()
+                        .map(|part| part.iter().nth(index).unwrap().1.to_string())
+                        .collect();
+                    println!(
+                        "    {:<8}{:<21} {}",
+                        parameter.name,
+                        values.join(" / "),
+                        parameter.description
+                    );
+                }
+            }
+            ["set", name, value] => match parameters(day).iter().find(|p| p.name == *name) {
+                Some(parameter) => match parameter.parse(value) {
+                    Ok(_) => overrides.insert(name, value),
+                    Err(message) => println!("{}", message),
+                },
+                None => println!("Unknown parameter: {}", name),
+            },
+            ["reset", name] => {
+                if !overrides.remove(name) {
+                    println!("{} was not set", name);
+                }
+            }
+            _ => println!("Unknown command, type `help` for the commands"),
+        }
+    }
+
+    Ok(())
+}
+
+fn run(session: &dyn Session, day: u8, part: u8, overrides: &Overrides) {
+    let parameters = match Parameters::resolve(parameters(day), part, overrides) {
+        Ok(parameters) => parameters,
+        Err(message) => return println!("{}", message),
+    };
+    let start = Instant::now();
+    match guarded(|| Ok(session.run(part, &parameters))) {
+        Err(message) => println!("Part {}: {}", part, message),
+        Ok(Some(answer)) if parameters.is_empty() => {
+            println!("Part {}: {} ({:.3?})", part, answer, start.elapsed())
+        }
+        Ok(Some(answer)) => println!(
+            "Part {}: {} ({:.3?}, {})",
+            part,
+            answer,
+            start.elapsed(),
+            parameters
+        ),
+        Ok(None) => println!("There is no part {}", part),
+    }
+}
+
+/// Runs a command of the session, turning a panic into an error so the parsed input is kept.
+fn guarded<T>(command: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    // The message is printed below, without the backtrace the default hook adds
+    let hook = take_hook();
+    set_hook(Box::new(|_| {}));
+    let result = catch_unwind(AssertUnwindSafe(command));
+    set_hook(hook);
+    result.unwrap_or_else(|payload| Err(format!("Failed: {}", panic_reason(payload.as_ref()))))
+}
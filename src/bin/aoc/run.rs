@@ -0,0 +1,50 @@
+use crate::repl::default_input;
+use advent_of_code::params::Overrides;
+use advent_of_code::solver::{solve, YEAR};
+use std::path::PathBuf;
+
+pub fn run(args: &[String]) -> Result<(), String> {
+    let usage = || {
+        "Usage: aoc run <day> [part] [--input <file>] [--param <name=value>]... [--config <file>]"
+    };
+    let day = args.first().ok_or_else(usage)?;
+    let day = day
+        .parse::<u8>()
+        .map_err(|_| format!("Not a day: {}", day))?;
+    let (overrides, rest) = Overrides::from_args(&args[1..], day)?;
+
+    let mut parts = vec![1, 2];
+    let mut path = None;
+    let mut rest = rest.iter();
+    while let Some(arg) = rest.next() {
+        match (arg.as_str(), arg.parse::<u8>()) {
+            ("--input", _) => path = Some(PathBuf::from(rest.next().ok_or_else(usage)?)),
+            (_, Ok(part)) => parts = vec![part],
+            _ => return Err(usage().to_string()),
+        }
+    }
+    let path = match path {
+        Some(path) => path,
+        None => default_input(day)?,
+    };
+    let input =
+        std::fs::read_to_string(&path).map_err(|error| format!("{}: {}", path.display(), error))?;
+
+    for part in parts {
+        let solution =
+            solve(YEAR, day, part, &input, &overrides, false).map_err(|error| error.to_string())?;
+        if solution.parameters.is_empty() {
+            println!(
+                "Part {}: {} ({:.3?})",
+                part, solution.answer, solution.elapsed
+            );
+        } else {
+            println!(
+                "Part {}: {} ({:.3?}, {})",
+                part, solution.answer, solution.elapsed, solution.parameters
+            );
+        }
+    }
+
+    Ok(())
+}
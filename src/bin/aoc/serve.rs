@@ -0,0 +1,131 @@
+use advent_of_code::params::Overrides;
+use advent_of_code::solver::{solve, SolveError};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Read;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+/// Puzzle inputs are some kilobytes, anything past this is refused instead of read into memory.
+const MAX_INPUT: u64 = 1 << 20;
+
+#[derive(Serialize)]
+struct Answer {
+    year: u16,
+    day: u8,
+    part: u8,
+    answer: String,
+    elapsed_ms: f64,
+    parameters: BTreeMap<&'static str, String>,
+    visualization: Option<String>,
+}
+
+#[derive(Serialize)]
+struct Failure {
+    error: String,
+}
+
+pub fn serve(args: &[String]) -> Result<(), String> {
+    let mut host = "127.0.0.1".to_string();
+    let mut port = 2021u16;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--host", Some(value)) => host = value.clone(),
+            ("--port", Some(value)) => {
+                port = value
+                    .parse()
+                    .map_err(|_| format!("Not a port number: {}", value))?
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    let server = Server::http((host.as_str(), port)).map_err(|error| error.to_string())?;
+    println!("Listening on http://{}:{}", host, port);
+    for request in server.incoming_requests() {
+        handle(request);
+    }
+
+    Ok(())
+}
+
+fn handle(mut request: Request) {
+    let (status, body) = match route(&mut request) {
+        Ok(answer) => (200, serde_json::to_string(&answer)),
+        Err((status, error)) => (status, serde_json::to_string(&Failure { error })),
+    };
+    println!("{} {} -> {}", request.method(), request.url(), status);
+
+    let header = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.unwrap())
+        .with_status_code(status)
+        .with_header(header);
+    if let Err(error) = request.respond(response) {
+        eprintln!("Could not respond: {}", error);
+    }
+}
+
+fn route(request: &mut Request) -> Result<Answer, (u16, String)> {
+    let url = request.url().to_string();
+    let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+    let not_found = || {
+        (
+            404,
+            format!("Expected POST /<year>/<day>/<part>, got {}", path),
+        )
+    };
+
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    let (year, day, part) = match segments.as_slice() {
+        [year, day, part] => (
+            year.parse::<u16>().map_err(|_| not_found())?,
+            day.parse::<u8>().map_err(|_| not_found())?,
+            part.parse::<u8>().map_err(|_| not_found())?,
+        ),
+        _ => return Err(not_found()),
+    };
+    if request.method() != &Method::Post {
+        return Err((405, "Post the puzzle input as the request body".to_string()));
+    }
+
+    // Every query pair other than visualize overrides a parameter of the day
+    let mut visualize = false;
+    let mut overrides = Overrides::default();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        match pair {
+            "visualize" | "visualize=true" | "visualize=1" => visualize = true,
+            _ => overrides
+                .assign(pair)
+                .map_err(|error| (400, error.to_string()))?,
+        }
+    }
+    let mut input = String::new();
+    request
+        .as_reader()
+        .take(MAX_INPUT + 1)
+        .read_to_string(&mut input)
+        .map_err(|_| (400, "Input is not valid UTF-8".to_string()))?;
+    if input.len() as u64 > MAX_INPUT {
+        return Err((413, format!("Input is larger than {} bytes", MAX_INPUT)));
+    }
+
+    match solve(year, day, part, &input, &overrides, visualize) {
+        Ok(solution) => Ok(Answer {
+            year,
+            day,
+            part,
+            answer: solution.answer,
+            elapsed_ms: solution.elapsed.as_secs_f64() * 1000.0,
+            parameters: solution
+                .parameters
+                .iter()
+                .map(|(name, value)| (*name, value.to_string()))
+                .collect(),
+            visualization: solution.visualization,
+        }),
+        Err(error @ SolveError::UnknownPuzzle { .. }) => Err((404, error.to_string())),
+        Err(error @ (SolveError::InvalidParameter(_) | SolveError::InvalidInput(_))) => {
+            Err((400, error.to_string()))
+        }
+    }
+}
@@ -0,0 +1,647 @@
+use advent_of_code::days::bingo::{
+    self, bingo_loser, bingo_winner, prepare, ranking, ranking_table, Board, Lines, Win, WinRule,
+};
+use advent_of_code::days::diagnostic::{
+    problems, rates, DiagnosticError, TiePolicy, CO2_SCRUB, O2_GEN,
+};
+use advent_of_code::days::diagnostic_stats::{bias_chart, statistics, WIDTH};
+use advent_of_code::days::diagnostic_trie::Trie;
+use advent_of_code::days::dive::{self, navigate, BreachPolicy, Direction};
+use advent_of_code::days::hydrothermal_venture::{
+    count_intersections, generate_field, input_to_lines, render_field, Line,
+};
+use advent_of_code::days::lanternfish::{get_init_state, Shoaling, PARAMETERS};
+use advent_of_code::days::seven_segment::{count_unique_digits, read_input_digits, Display, Entry};
+use advent_of_code::days::smoke_basin::{
+    find_basins, find_low_points, largest_basins, read_input_to_map, HeightMap, Point,
+};
+use advent_of_code::days::sonar_sweep::{filtered_sonar_sweep, read_depths, Depth};
+use advent_of_code::days::syntax_scoring::{completion_score_into, read_chunks, Stack};
+use advent_of_code::days::whales::{
+    crab_positions, fuel_function, mean, median, mode, optimize_alignment,
+};
+use advent_of_code::params::{Parameters, Value};
+use advent_of_code::theme::Theme;
+use std::io::sink;
+use std::ops::Range;
+
+/// A day's input, parsed once and kept around to be inspected and solved repeatedly.
+pub trait Session {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String>;
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)];
+
+    /// `parameters` are the ones of the first part, for topics depending on them.
+    fn show(&self, topic: &str, args: &[&str], parameters: &Parameters) -> Result<String, String>;
+}
+
+/// `Err` when the input cannot be parsed, or there is no session for the day.
+pub fn session<'a>(day: u8, input: &'a str) -> Result<Box<dyn Session + 'a>, String> {
+    let lines: Vec<&str> = input
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    Ok(match day {
+        // Whole depths are kept exact, fractional ones (or NaN for a gap) read as f64
+        1 => match read_depths::<i64, _>(input.as_bytes()).collect() {
+            Ok(depths) => Box::new(SonarSweep { depths }),
+            Err(_) => Box::new(SonarSweep::<f64> {
+                depths: read_depths(input.as_bytes())
+                    .collect::<Result<_, _>>()
+                    .map_err(|error| error.to_string())?,
+            }),
+        },
+        2 => Box::new(Dive {
+            directions: lines
+                .iter()
+                .enumerate()
+                .map(|(index, line)| {
+                    line.parse()
+                        .map_err(|reason| format!("Command {} ({}): {}", index, line, reason))
+                })
+                .collect::<Result<_, _>>()?,
+        }),
+        3 => Box::new(Diagnostic {
+            trie: Trie::new(&lines),
+            lines,
+        }),
+        4 => {
+            let (order, boards) = prepare(input).map_err(|error| error.to_string())?;
+            Box::new(Bingo { order, boards })
+        }
+        5 => Box::new(HydrothermalVenture {
+            lines: input_to_lines(lines)?,
+        }),
+        6 => Box::new(Lanternfish {
+            ages: get_init_state(input.to_string())?,
+        }),
+        7 => Box::new(Whales {
+            positions: crab_positions(input)?,
+        }),
+        8 => Box::new(SevenSegment {
+            entries: read_input_digits(input)?,
+        }),
+        9 => {
+            let rows = read_input_to_map(input)?;
+            let low_points = find_low_points(&rows);
+            Box::new(SmokeBasin { rows, low_points })
+        }
+        10 => Box::new(SyntaxScoring {
+            lines: read_chunks(input)?,
+        }),
+        _ => return Err(format!("No solver for day {}", day)),
+    })
+}
+
+fn index(args: &[&str], len: usize) -> Result<usize, String> {
+    let index = args
+        .first()
+        .ok_or("Which one? Pass an index")?
+        .parse::<usize>()
+        .map_err(|_| "Not an index")?;
+    if index < len {
+        Ok(index)
+    } else {
+        Err(format!("Index out of bounds, there are {}", len))
+    }
+}
+
+fn range(args: &[&str], len: usize) -> Result<Range<usize>, String> {
+    let bound = |arg: Option<&&str>, default: usize| {
+        arg.map(|arg| {
+            arg.parse::<usize>()
+                .map_err(|_| format!("Not an index: {}", arg))
+        })
+        .unwrap_or(Ok(default))
+        .map(|bound| bound.min(len))
+    };
+    let start = bound(args.first(), 0)?;
+    let end = bound(args.get(1), start + 20)?;
+    Ok(start..end.max(start))
+}
+
+fn list<T: std::fmt::Display>(items: &[T], args: &[&str]) -> Result<String, String> {
+    let range = range(args, items.len())?;
+    let mut listing: Vec<String> = items[range.clone()]
+        .iter()
+        .enumerate()
+        .map(|(index, item)| format!("{:>5}: {}", range.start + index, item))
+        .collect();
+    listing.push(format!("({} of {})", range.len(), items.len()));
+    Ok(listing.join("\n"))
+}
+
+struct SonarSweep<D> {
+    depths: Vec<D>,
+}
+
+impl<D: Depth> Session for SonarSweep<D> {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        match part {
+            1 | 2 => Some(
+                filtered_sonar_sweep(self.depths.clone(), parameters.integer("window") as usize)
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[("depths [from] [to]", "the sonar readings")]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "depths" => list(&self.depths, args),
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct Dive {
+    directions: Vec<Direction>,
+}
+
+impl Session for Dive {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        if !(1..=2).contains(&part) {
+            return None;
+        }
+        let policy = parameters
+            .choice("surface")
+            .parse::<BreachPolicy>()
+            .unwrap();
+        let model = dive::model(parameters.choice("model")).unwrap();
+        Some(match navigate(&self.directions, model, policy) {
+            Ok(product) => product.to_string(),
+            Err(error) => error.to_string(),
+        })
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[("commands [from] [to]", "the parsed directions")]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "commands" => list(&self.directions, args),
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct Diagnostic<'a> {
+    lines: Vec<&'a str>,
+    /// Built once, to filter the report by every criteria asked for.
+    trie: Result<Trie, DiagnosticError>,
+}
+
+impl Session for Diagnostic<'_> {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        let policy = parameters.choice("ties").parse::<TiePolicy>().unwrap();
+        let answer = match part {
+            1 => rates(&self.lines, policy).map(|rates| {
+                let answer = &rates.gamma * &rates.epsilon;
+                (answer, rates.ties)
+            }),
+            2 => self.trie.as_ref().map_err(Clone::clone).and_then(|trie| {
+                let oxygen = trie.rating(O2_GEN, policy)?;
+                let co2 = trie.rating(CO2_SCRUB, policy)?;
+                let ties = [oxygen.ties, co2.ties].concat();
+                Ok((oxygen.value * co2.value, ties))
+            }),
+            _ => return None,
+        };
+        Some(match answer {
+            Ok((answer, ties)) if ties.is_empty() => answer.to_string(),
+            Ok((answer, ties)) => format!("{} (ties at bits {:?})", answer, ties),
+            Err(error) => error.to_string(),
+        })
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("lines [from] [to]", "the report"),
+            (
+                "counts",
+                "the number of ones and zeros per bit, and their bias",
+            ),
+            (
+                "problems",
+                "the lines that are not as wide as the first or not bits",
+            ),
+            (
+                "prefix <bits>",
+                "the number of lines starting with the bits",
+            ),
+        ]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "lines" => list(&self.lines, args),
+            "counts" => statistics(&self.lines)
+                .map(|statistics| bias_chart(&statistics, WIDTH, Theme::current()))
+                .map_err(|error| error.to_string()),
+            "problems" => {
+                let problems = problems(&self.lines);
+                let mut listing: Vec<String> = problems.iter().map(ToString::to_string).collect();
+                listing.push(format!(
+                    "({} of {} lines)",
+                    problems.len(),
+                    self.lines.len()
+                ));
+                Ok(listing.join("\n"))
+            }
+            "prefix" => {
+                let prefix = args.first().ok_or("Which bits?")?;
+                let trie = self.trie.as_ref().map_err(|error| error.to_string())?;
+                Ok(format!(
+                    "{} lines start with {}",
+                    trie.count_prefix(prefix),
+                    prefix
+                ))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct Bingo {
+    order: Vec<u32>,
+    boards: Vec<Board>,
+}
+
+impl Session for Bingo {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        let rule = bingo::rule(parameters.choice("rule")).unwrap();
+        let score = match part {
+            1 => bingo_winner(&self.order, &self.boards, rule.as_ref()),
+            2 => bingo_loser(&self.order, &self.boards, rule.as_ref()),
+            _ => return None,
+        };
+        Some(score.map_or("No board ever wins".to_string(), |score| score.to_string()))
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("order", "the numbers in the order they are drawn"),
+            (
+                "boards [rule]",
+                "every board with the turn it wins on, by lines or the rule",
+            ),
+            ("board <index>", "a single board"),
+            (
+                "ranking [rule]",
+                "the boards in the order they win, by lines or the rule",
+            ),
+        ]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "order" => Ok(format!("{:?}", self.order)),
+            "boards" | "ranking" => {
+                let rule: Box<dyn WinRule> = match args.first() {
+                    Some(name) => bingo::rule(name)?,
+                    None => Box::new(Lines),
+                };
+                let rule = rule.as_ref();
+                if topic == "ranking" {
+                    return Ok(ranking_table(&ranking(&self.order, &self.boards, rule)));
+                }
+                Ok(self
+                    .boards
+                    .iter()
+                    .enumerate()
+                    .map(|(index, board)| match board.win(&self.order, rule) {
+                        Some(Win {
+                            turn,
+                            number,
+                            score,
+                        }) => format!(
+                            "board {:>3}: wins on turn {:>3} when {:>2} is drawn, score {}",
+                            index, turn, number, score
+                        ),
+                        None => format!("board {:>3}: never wins", index),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("\n"))
+            }
+            "board" => Ok(self.boards[index(args, self.boards.len())?].to_string()),
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct HydrothermalVenture {
+    lines: Vec<Line>,
+}
+
+impl HydrothermalVenture {
+    fn field(&self, straight_only: bool) -> Vec<Vec<usize>> {
+        generate_field(
+            self.lines
+                .iter()
+                .filter(|line| !straight_only || line.is_horizontal() || line.is_vertical())
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+impl Session for HydrothermalVenture {
+    fn run(&self, part: u8, _parameters: &Parameters) -> Option<String> {
+        match part {
+            1 | 2 => Some(count_intersections(self.field(part == 1)).to_string()),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[(
+            "field [straight]",
+            "the vents, optionally only horizontal and vertical ones",
+        )]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "field" => Ok(render_field(
+                &self.field(args.first() == Some(&"straight")),
+                Theme::current(),
+            )),
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct Lanternfish {
+    ages: Vec<u8>,
+}
+
+impl Session for Lanternfish {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        match part {
+            1 | 2 => Some(
+                Shoaling::from_ages(self.ages.clone())
+                    .progress_n_days(parameters.integer("days"))
+                    .fish_count()
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[("timers [days]", "how many fish have each timer value")]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "timers" => {
+                let days = match args.first().map(|days| PARAMETERS[0].parse(days)) {
+                    Some(Ok(Value::Integer(days))) => days,
+                    Some(Err(message)) => return Err(message),
+                    _ => 0,
+                };
+                let shoaling = Shoaling::from_ages(self.ages.clone()).progress_n_days(days);
+                Ok((0..=8u8)
+                    .map(|timer| format!("timer {}: {}", timer, shoaling[timer]))
+                    .collect::<Vec<String>>()
+                    .join("\n"))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct Whales {
+    positions: Vec<usize>,
+}
+
+impl Session for Whales {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        if !(1..=2).contains(&part) {
+            return None;
+        }
+        let (position, fuel) =
+            optimize_alignment(&self.positions, fuel_function(parameters.choice("fuel")));
+        Some(format!("{} (moving to {})", fuel, position))
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[("positions", "statistics of the crab positions")]
+    }
+
+    fn show(
+        &self,
+        topic: &str,
+        _args: &[&str],
+        _parameters: &Parameters,
+    ) -> Result<String, String> {
+        match topic {
+            "positions" => {
+                let (Some(lowest), Some(highest)) =
+                    (self.positions.iter().min(), self.positions.iter().max())
+                else {
+                    return Err("There are no crabs".to_string());
+                };
+                Ok(format!(
+                    "{} crabs between {} and {}, mean {}, median {}, mode {}",
+                    self.positions.len(),
+                    lowest,
+                    highest,
+                    mean(&self.positions),
+                    median(&self.positions),
+                    mode(&self.positions)
+                ))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct SevenSegment<'a> {
+    entries: Vec<Entry<'a>>,
+}
+
+impl Session for SevenSegment<'_> {
+    fn run(&self, part: u8, _parameters: &Parameters) -> Option<String> {
+        let entries = self.entries.clone().into_iter();
+        match part {
+            1 => Some(
+                entries
+                    .map(|(_, output)| count_unique_digits(output))
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            2 => Some(
+                entries
+                    .map(|(notes, output)| Display::from_notes(notes).decode_number(output))
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("entry <index>", "the notes and output of an entry"),
+            ("display <index>", "the decoded output of an entry"),
+        ]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        let entry = || Ok::<_, String>(self.entries[index(args, self.entries.len())?].clone());
+        match topic {
+            "entry" => {
+                let (notes, output) = entry()?;
+                Ok(format!("{} | {}", notes.join(" "), output.join(" ")))
+            }
+            "display" => {
+                let (notes, output) = entry()?;
+                let theme = Theme::current();
+                let number = Display::from_notes(notes).decode_number(output);
+                Ok(Display::display(number, theme, theme.palette.digit))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct SmokeBasin {
+    rows: Vec<Vec<usize>>,
+    low_points: Vec<Point>,
+}
+
+impl Session for SmokeBasin {
+    fn run(&self, part: u8, parameters: &Parameters) -> Option<String> {
+        match part {
+            1 => Some(
+                self.low_points
+                    .iter()
+                    .map(|point| point.risk())
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            2 => Some(
+                largest_basins(
+                    &self.rows,
+                    &self.low_points,
+                    parameters.integer("basins") as usize,
+                    parameters.integer("wall") as usize,
+                )
+                .to_string(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[
+            ("map", "the height map with its low points"),
+            (
+                "basin <index>",
+                "the heights within the basin of a low point",
+            ),
+        ]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "map" => Ok(HeightMap {
+                rows: self.rows.clone(),
+                low_points: self.low_points.clone(),
+            }
+            .render(Theme::current())),
+            "basin" => {
+                let low_point = &self.low_points[index(args, self.low_points.len())?];
+                let basin = find_basins(
+                    &self.rows,
+                    vec![(low_point.row, low_point.col)],
+                    parameters.integer("wall") as usize,
+                );
+                let rows = basin.keys().map(|(row, _)| *row);
+                let cols = basin.keys().map(|(_, col)| *col);
+                let (Some(top), Some(bottom), Some(left), Some(right)) = (
+                    rows.clone().min(),
+                    rows.max(),
+                    cols.clone().min(),
+                    cols.max(),
+                ) else {
+                    return Err(
+                        "The low point is not below the wall, there is no basin".to_string()
+                    );
+                };
+                let rendering: Vec<String> = (top..=bottom)
+                    .map(|row| {
+                        (left..=right)
+                            .map(|col| match basin.contains_key(&(row, col)) {
+                                true => self.rows[row][col].to_string(),
+                                false => " ".to_string(),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                Ok(format!(
+                    "{}\n{} locations",
+                    rendering.join("\n"),
+                    basin.len()
+                ))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
+
+struct SyntaxScoring<'a> {
+    lines: Vec<&'a str>,
+}
+
+impl Session for SyntaxScoring<'_> {
+    fn run(&self, part: u8, _parameters: &Parameters) -> Option<String> {
+        let theme = Theme::current();
+        match part {
+            1 => Some(
+                self.lines
+                    .iter()
+                    .map(|line| Stack::corruption_score_into(line, &mut sink(), theme))
+                    .sum::<usize>()
+                    .to_string(),
+            ),
+            2 => Some(
+                completion_score_into(&self.lines.join("\n"), &mut sink(), theme)
+                    .map_or("no line is incomplete".to_string(), |score| {
+                        score.to_string()
+                    }),
+            ),
+            _ => None,
+        }
+    }
+
+    fn topics(&self) -> &'static [(&'static str, &'static str)] {
+        &[("line <index>", "how a line gets parsed and completed")]
+    }
+
+    fn show(&self, topic: &str, args: &[&str], _parameters: &Parameters) -> Result<String, String> {
+        match topic {
+            "line" => {
+                let line = self.lines[index(args, self.lines.len())?];
+                let theme = Theme::current();
+                let mut trace = Vec::new();
+                let score = Stack::corruption_score_into(line, &mut trace, theme);
+                let summary = match Stack::complete_into(line, &mut sink(), theme) {
+                    Some((completion, score)) => {
+                        format!("Incomplete, completed by {} (score {})", completion, score)
+                    }
+                    _ => format!("Corrupt (score {})", score),
+                };
+                Ok(format!("{}{}", String::from_utf8_lossy(&trace), summary))
+            }
+            _ => Err(format!("Unknown topic: {}", topic)),
+        }
+    }
+}
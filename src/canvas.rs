@@ -0,0 +1,78 @@
+use crate::theme::{Theme, ThemeColor};
+
+const DOTS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// A grid of braille characters to draw charts in the terminal. Every character holds two by
+/// four dots, `y` counting down from the top.
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    cells: Vec<Vec<(u8, Option<ThemeColor>)>>,
+}
+
+impl Canvas {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![vec![(0, None); width]; height],
+        }
+    }
+
+    /// The number of dots horizontally and vertically.
+    pub fn dots(&self) -> (usize, usize) {
+        (self.width * 2, self.height * 4)
+    }
+
+    /// Sets a dot, coloring its character when a color is given. Dots off the canvas are ignored.
+    pub fn set(&mut self, x: usize, y: usize, color: Option<ThemeColor>) {
+        if let Some(cell) = self.cells.get_mut(y / 4).and_then(|row| row.get_mut(x / 2)) {
+            cell.0 |= DOTS[x % 2][y % 4];
+            cell.1 = color.or(cell.1);
+        }
+    }
+
+    pub fn line(&mut self, from: (usize, usize), to: (usize, usize), color: Option<ThemeColor>) {
+        let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1)).max(1);
+        let lerp = |a: usize, b: usize, step: usize| {
+            (a as f64 + (b as f64 - a as f64) * step as f64 / steps as f64).round() as usize
+        };
+        for step in 0..=steps {
+            self.set(lerp(from.0, to.0, step), lerp(from.1, to.1, step), color);
+        }
+    }
+
+    pub fn render(&self, theme: &Theme) -> Vec<String> {
+        self.cells
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&(dots, color)| {
+                        let dots = char::from_u32(0x2800 + dots as u32).unwrap().to_string();
+                        match color {
+                            Some(color) => theme.paint(&dots, color).to_string(),
+                            None => theme.glyphs(&dots).into_owned(),
+                        }
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::canvas::Canvas;
+    use crate::theme::Theme;
+
+    #[test]
+    fn test_canvas() {
+        let mut canvas = Canvas::new(2, 1);
+        assert_eq!((4, 4), canvas.dots());
+        canvas.line((0, 0), (3, 3), None);
+        canvas.set(10, 10, None);
+        let mut plain = Theme::plain();
+        plain.capabilities.unicode = true;
+        assert_eq!(vec!["⠑⢄"], canvas.render(&plain));
+    }
+}
@@ -0,0 +1,747 @@
+use crate::params::Parameter;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static PARAMETERS: [Parameter; 1] = [Parameter::choice(
+    "rule",
+    "the fields a card needs marked to win, or pattern:<mask> for a pattern",
+    &["lines", "diagonals", "corners", "blackout"],
+    ["lines", "lines"],
+)
+.or_prefixed("pattern", |mask| {
+    mask.parse::<Pattern>()
+        .map(|_| ())
+        .map_err(|reason| reason.to_string())
+})];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Field {
+    turn: usize,
+    num: u32,
+}
+
+impl Field {
+    fn new(turn: usize, num: u32) -> Field {
+        Field { turn, num }
+    }
+}
+
+type Fields = Vec<Field>;
+
+/// A card of any number of rows and columns, every field knowing the turn it is drawn at.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    rows: Vec<Fields>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BingoError {
+    /// The input has no line of numbers to draw.
+    NoDraws,
+    /// The draw at `position` of the first line is not a number.
+    Draw {
+        position: usize,
+        value: String,
+    },
+    NoBoards,
+    /// The field at `row` and `col` of board `board`, all counting from 0, is not a number.
+    Number {
+        board: usize,
+        row: usize,
+        col: usize,
+        value: String,
+    },
+    /// Row `row` of board `board` has `width` numbers instead of the `expected` of its first row.
+    Ragged {
+        board: usize,
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl Display for BingoError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BingoError::NoDraws => write!(f, "No numbers are drawn"),
+            BingoError::Draw { position, value } => {
+                write!(f, "Draw {} is not a number: {:?}", position, value)
+            }
+            BingoError::NoBoards => write!(f, "There are no boards"),
+            BingoError::Number {
+                board,
+                row,
+                col,
+                value,
+            } => write!(
+                f,
+                "Board {} row {} column {} is not a number: {:?}",
+                board, row, col, value
+            ),
+            BingoError::Ragged {
+                board,
+                row,
+                width,
+                expected,
+            } => write!(
+                f,
+                "Board {} row {} has {} numbers instead of {}",
+                board, row, width, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BingoError {}
+
+impl Board {
+    /// The card's shape is that of the numbers in `lines`, a row per line and separated by any
+    /// whitespace. Numbers that are never drawn are never marked.
+    fn new(
+        index: usize,
+        lines: &[&str],
+        draw_order: &HashMap<u32, usize>,
+    ) -> Result<Self, BingoError> {
+        let rows = lines
+            .iter()
+            .enumerate()
+            .map(|(row, line)| {
+                line.split_whitespace()
+                    .enumerate()
+                    .map(|(col, numstr)| {
+                        let number = numstr.parse::<u32>().map_err(|_| BingoError::Number {
+                            board: index,
+                            row,
+                            col,
+                            value: numstr.to_string(),
+                        })?;
+                        let turn = draw_order.get(&number).copied().unwrap_or(usize::MAX);
+                        Ok(Field::new(turn, number))
+                    })
+                    .collect::<Result<Fields, BingoError>>()
+            })
+            .collect::<Result<Vec<Fields>, BingoError>>()?;
+        let width = rows[0].len();
+        if let Some((row, fields)) = rows
+            .iter()
+            .enumerate()
+            .find(|(_, fields)| fields.len() != width)
+        {
+            return Err(BingoError::Ragged {
+                board: index,
+                row,
+                width: fields.len(),
+                expected: width,
+            });
+        }
+        Ok(Board { rows })
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows[0].len()
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Every number on the card, row by row.
+    pub fn numbers(&self) -> impl Iterator<Item = u32> + '_ {
+        self.rows.iter().flatten().map(|field| field.num)
+    }
+
+    pub fn number(&self, row: usize, col: usize) -> u32 {
+        self.rows[row][col].num
+    }
+
+    /// Whether the field at `row` and `col` has been drawn by `turn`.
+    pub fn marked(&self, row: usize, col: usize, turn: usize) -> bool {
+        self.rows[row][col].turn <= turn
+    }
+
+    /// A complete row or column, the puzzle's rule.
+    pub fn winner(&self, turn: usize) -> bool {
+        Lines.wins(self, turn)
+    }
+
+    /// The turn the first set of fields the rule wins with is complete, out of `draws` turns.
+    pub fn winning_turn(&self, draws: usize, rule: &dyn WinRule) -> Option<usize> {
+        rule.candidates(self)
+            .iter()
+            .map(|fields| {
+                fields
+                    .iter()
+                    .map(|&(row, col)| self.rows[row][col].turn)
+                    .max()
+                    .unwrap_or(0)
+            })
+            .min()
+            .filter(|&turn| turn < draws)
+    }
+
+    pub fn win(&self, order: &[u32], rule: &dyn WinRule) -> Option<Win> {
+        let turn = self.winning_turn(order.len(), rule)?;
+        Some(Win {
+            turn,
+            number: order[turn],
+            score: order[turn] as u64 * self.sum_of_rest(turn),
+        })
+    }
+
+    pub fn sum_of_rest(&self, turn: usize) -> u64 {
+        self.rows
+            .iter()
+            .flat_map(|row| row.iter())
+            .filter(|field| field.turn > turn)
+            .map(|field| field.num as u64)
+            .sum()
+    }
+}
+
+impl Display for Board {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let fields = self.rows.iter().flat_map(|row| row.iter());
+        let width = fields.map(|field| field.num.to_string().len()).max();
+        for row in self.rows.iter() {
+            let numbers: Vec<String> = row
+                .iter()
+                .map(|field| format!("{:>width$}", field.num, width = width.unwrap_or(0)))
+                .collect();
+            writeln!(f, "{}", numbers.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// A field of a card, by row and column.
+pub type Position = (usize, usize);
+
+/// What a card needs marked to win.
+pub trait WinRule {
+    fn name(&self) -> &'static str;
+
+    /// The sets of fields of `board` that win once all of their numbers are drawn.
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>>;
+
+    /// The first set of fields that is marked by `turn`.
+    fn winning_fields(&self, board: &Board, turn: usize) -> Option<Vec<Position>> {
+        self.candidates(board).into_iter().find(|fields| {
+            fields
+                .iter()
+                .all(|&(row, col)| board.marked(row, col, turn))
+        })
+    }
+
+    fn wins(&self, board: &Board, turn: usize) -> bool {
+        self.winning_fields(board, turn).is_some()
+    }
+}
+
+impl<R: WinRule + ?Sized> WinRule for &R {
+    fn name(&self) -> &'static str {
+        (**self).name()
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        (**self).candidates(board)
+    }
+
+    fn winning_fields(&self, board: &Board, turn: usize) -> Option<Vec<Position>> {
+        (**self).winning_fields(board, turn)
+    }
+
+    fn wins(&self, board: &Board, turn: usize) -> bool {
+        (**self).wins(board, turn)
+    }
+}
+
+/// A complete row or column, the puzzle's rule.
+pub struct Lines;
+
+impl WinRule for Lines {
+    fn name(&self) -> &'static str {
+        "lines"
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        let rows =
+            (0..board.height()).map(|row| (0..board.width()).map(|col| (row, col)).collect());
+        let cols =
+            (0..board.width()).map(|col| (0..board.height()).map(|row| (row, col)).collect());
+        rows.chain(cols).collect()
+    }
+}
+
+/// A complete row or column, or on square cards a complete diagonal.
+pub struct Diagonals;
+
+impl WinRule for Diagonals {
+    fn name(&self) -> &'static str {
+        "diagonals"
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        let mut candidates = Lines.candidates(board);
+        let size = board.width();
+        if size == board.height() {
+            candidates.push((0..size).map(|index| (index, index)).collect());
+            candidates.push((0..size).map(|index| (index, size - 1 - index)).collect());
+        }
+        candidates
+    }
+}
+
+/// All four corners.
+pub struct Corners;
+
+impl WinRule for Corners {
+    fn name(&self) -> &'static str {
+        "corners"
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        let (right, bottom) = (board.width() - 1, board.height() - 1);
+        vec![vec![(0, 0), (0, right), (bottom, 0), (bottom, right)]]
+    }
+}
+
+/// Every field of the card.
+pub struct Blackout;
+
+impl WinRule for Blackout {
+    fn name(&self) -> &'static str {
+        "blackout"
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        vec![(0..board.height())
+            .flat_map(|row| (0..board.width()).map(move |col| (row, col)))
+            .collect()]
+    }
+}
+
+/// A mask of fields that wins wherever it fits on the card, like `x.x/.x./x.x` for an X in any
+/// 3 by 3 square.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Pattern {
+    mask: Vec<Vec<bool>>,
+}
+
+impl FromStr for Pattern {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let mask = string
+            .split('/')
+            .map(|row| {
+                row.chars()
+                    .map(|character| match character {
+                        'x' => Ok(true),
+                        '.' => Ok(false),
+                        _ => Err("Patterns are rows of x and . separated by /"),
+                    })
+                    .collect()
+            })
+            .collect::<Result<Vec<Vec<bool>>, _>>()?;
+        if mask.iter().any(|row| row.len() != mask[0].len()) {
+            return Err("The rows of a pattern differ in length");
+        }
+        if !mask.iter().flatten().any(|&field| field) {
+            return Err("A pattern needs at least one x");
+        }
+        Ok(Pattern { mask })
+    }
+}
+
+impl WinRule for Pattern {
+    fn name(&self) -> &'static str {
+        "pattern"
+    }
+
+    fn candidates(&self, board: &Board) -> Vec<Vec<Position>> {
+        let (height, width) = (self.mask.len(), self.mask[0].len());
+        if height > board.height() || width > board.width() {
+            return Vec::new();
+        }
+        let fields = |top: usize, left: usize| {
+            self.mask
+                .iter()
+                .enumerate()
+                .flat_map(|(row, fields)| {
+                    fields
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, &field)| field)
+                        .map(move |(col, _)| (top + row, left + col))
+                })
+                .collect()
+        };
+        (0..=board.height() - height)
+            .flat_map(|top| (0..=board.width() - width).map(move |left| (top, left)))
+            .map(|(top, left)| fields(top, left))
+            .collect()
+    }
+}
+
+pub const RULES: [&dyn WinRule; 4] = [&Lines, &Diagonals, &Corners, &Blackout];
+
+/// One of [RULES] by its name, or a [Pattern] given as `pattern:<mask>`.
+pub fn rule(name: &str) -> Result<Box<dyn WinRule>, String> {
+    if let Some(mask) = name.strip_prefix("pattern:") {
+        let pattern = mask
+            .parse::<Pattern>()
+            .map_err(|reason| format!("{}: {}", name, reason))?;
+        return Ok(Box::new(pattern));
+    }
+    RULES
+        .into_iter()
+        .find(|rule| rule.name() == name)
+        .map(|rule| Box::new(rule) as Box<dyn WinRule>)
+        .ok_or_else(|| format!("Unknown rule: {}", name))
+}
+
+/// The numbers in the order they are drawn, from the first line, and the boards below,
+/// separated by one or more blank lines.
+pub fn prepare(input: &str) -> Result<(Vec<u32>, Vec<Board>), BingoError> {
+    let mut lines = input
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+    let draw_order: Vec<u32> = lines
+        .next()
+        .ok_or(BingoError::NoDraws)?
+        .split(',')
+        .enumerate()
+        .map(|(position, string)| {
+            string.trim().parse::<u32>().map_err(|_| BingoError::Draw {
+                position,
+                value: string.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+    // A number drawn twice is marked the first time
+    let mut draw_order_map: HashMap<u32, usize> = HashMap::new();
+    for (index, num) in draw_order.iter().enumerate() {
+        draw_order_map.entry(*num).or_insert(index);
+    }
+
+    let lines: Vec<&str> = lines.collect();
+    let boards = lines
+        .split(|line| line.is_empty())
+        .filter(|board| !board.is_empty())
+        .enumerate()
+        .map(|(index, board)| Board::new(index, board, &draw_order_map))
+        .collect::<Result<Vec<Board>, BingoError>>()?;
+    if boards.is_empty() {
+        return Err(BingoError::NoBoards);
+    }
+
+    Ok((draw_order, boards))
+}
+
+/// When a board wins: the turn, the number drawn then and the score.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Win {
+    pub turn: usize,
+    pub number: u32,
+    pub score: u64,
+}
+
+/// A board's place in the game. Boards winning on the same turn share their rank, boards that
+/// never win come last.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Standing {
+    pub rank: usize,
+    pub board: usize,
+    pub win: Option<Win>,
+}
+
+/// Every board by the turn it wins on, boards winning together in the order they are listed.
+pub fn ranking(order: &[u32], boards: &[Board], rule: &dyn WinRule) -> Vec<Standing> {
+    let mut standings: Vec<Standing> = boards
+        .iter()
+        .enumerate()
+        .map(|(index, board)| Standing {
+            rank: 0,
+            board: index,
+            win: board.win(order, rule),
+        })
+        .collect();
+    standings.sort_by_key(|standing| {
+        (
+            standing.win.map_or(usize::MAX, |win| win.turn),
+            standing.board,
+        )
+    });
+
+    let mut previous: Option<(usize, Option<usize>)> = None;
+    for (place, standing) in standings.iter_mut().enumerate() {
+        let turn = standing.win.map(|win| win.turn);
+        standing.rank = match previous {
+            Some((rank, previous)) if previous == turn => rank,
+            _ => place + 1,
+        };
+        previous = Some((standing.rank, turn));
+    }
+    standings
+}
+
+/// The ranking as a table, `=` marking shared ranks.
+pub fn ranking_table(standings: &[Standing]) -> String {
+    let shared = |standing: &Standing| {
+        standings
+            .iter()
+            .filter(|other| other.rank == standing.rank)
+            .count()
+            > 1
+    };
+    let mut table = vec![format!(
+        "{:>5} {:>5} {:>5} {:>5} {:>8}",
+        "rank", "board", "turn", "drawn", "score"
+    )];
+    table.extend(standings.iter().map(|standing| match standing.win {
+        Some(Win {
+            turn,
+            number,
+            score,
+        }) => {
+            let rank = format!(
+                "{}{}",
+                if shared(standing) { "=" } else { "" },
+                standing.rank
+            );
+            format!(
+                "{:>5} {:>5} {:>5} {:>5} {:>8}",
+                rank, standing.board, turn, number, score
+            )
+        }
+        None => format!("{:>5} {:>5} {:>5}", "-", standing.board, "never wins"),
+    }));
+    table.join("\n")
+}
+
+/// The score of the first board to win, if any does.
+pub fn bingo_winner(order: &[u32], boards: &[Board], rule: &dyn WinRule) -> Option<u64> {
+    ranking(order, boards, rule)
+        .first()?
+        .win
+        .map(|win| win.score)
+}
+
+/// The score of the last board to win. Of the boards that win last together, the one listed last
+/// loses.
+pub fn bingo_loser(order: &[u32], boards: &[Board], rule: &dyn WinRule) -> Option<u64> {
+    ranking(order, boards, rule)
+        .iter()
+        .rev()
+        .find_map(|standing| standing.win)
+        .map(|win| win.score)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::bingo::{
+        bingo_loser, bingo_winner, prepare, ranking, ranking_table, rule, BingoError, Blackout,
+        Corners, Diagonals, Lines, Pattern, Standing, Win, WinRule,
+    };
+
+    const RAW: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn test_bingo_winner() {
+        let (order, boards) = prepare(RAW).unwrap();
+        assert_eq!(Some(4512), bingo_winner(&order, &boards, &Lines));
+        assert!(boards[2].winner(11) && !boards[2].winner(10));
+    }
+
+    #[test]
+    fn test_bingo_loser() {
+        let (order, boards) = prepare(RAW).unwrap();
+        assert_eq!(Some(1924), bingo_loser(&order, &boards, &Lines));
+    }
+
+    #[test]
+    fn test_board_shapes() {
+        let raw = "300,7,1000,42,256,9,12
+
+ 300  256    7
+1000   42    9
+
+  12   42    9
+ 256  300    7";
+        let (order, boards) = prepare(raw).unwrap();
+        assert_eq!(vec![300, 7, 1000, 42, 256, 9, 12], order);
+        assert_eq!((3, 2), (boards[0].width(), boards[0].height()));
+        assert_eq!(" 300  256    7\n1000   42    9\n", boards[0].to_string());
+        // The first column wins on 1000, leaving 256, 42 and 9
+        assert_eq!(Some(2), boards[0].winning_turn(order.len(), &Lines));
+        assert_eq!(Some(1000 * 307), bingo_winner(&order, &boards, &Lines));
+        // The middle column of the second board wins on 42
+        assert_eq!(Some(3), boards[1].winning_turn(order.len(), &Lines));
+        assert_eq!(
+            Some(42 * (12 + 9 + 256)),
+            bingo_loser(&order, &boards, &Lines)
+        );
+    }
+
+    #[test]
+    fn test_win_rules() {
+        let (order, boards) = prepare("5,1,9,3,7,2,4,6,8\n\n1 2 3\n4 5 6\n7 8 9").unwrap();
+        let turn = |rule: &dyn WinRule| boards[0].winning_turn(order.len(), rule);
+        // Draws 5, 1, 9, 3, 7 and 2 complete the top row
+        assert_eq!(Some(5), turn(&Lines));
+        assert_eq!(Some(2), turn(&Diagonals));
+        assert_eq!(Some(4), turn(&Corners));
+        assert_eq!(Some(8), turn(&Blackout));
+        assert_eq!(Some(8), turn(rule("blackout").unwrap().as_ref()));
+        assert_eq!(Some(3), turn(rule("pattern:x.x/.x.").unwrap().as_ref()));
+        assert!(rule("pattern:x.x/.x").is_err());
+        assert!(rule("stripes").is_err());
+        assert_eq!(Some(2 * (4 + 6 + 8)), bingo_winner(&order, &boards, &Lines));
+        assert_eq!(
+            Some(9 * (2 + 3 + 4 + 6 + 7 + 8)),
+            bingo_winner(&order, &boards, &Diagonals)
+        );
+
+        // Both cards only need their corners, drawn by 7, and the last listed loses
+        let (order, boards) =
+            prepare("5,1,9,3,7,2,4,6,8\n\n1 2 3\n4 5 6\n7 8 9\n\n1 2 3\n7 8 9").unwrap();
+        assert_eq!(Some(7 * (2 + 8)), bingo_loser(&order, &boards, &Corners));
+
+        // Patterns win wherever they fit
+        let pattern = |mask: &str| mask.parse::<Pattern>().unwrap();
+        assert_eq!(Some(1), turn(&pattern("x./.x")));
+        assert_eq!(Some(3), turn(&pattern(".x/x.")));
+        assert_eq!(turn(&Corners), turn(&pattern("x.x/.../x.x")));
+        assert_eq!(None, turn(&pattern("xxxx")));
+        assert!("x./xx.".parse::<Pattern>().is_err());
+        assert!("../..".parse::<Pattern>().is_err());
+        assert!("x-x".parse::<Pattern>().is_err());
+    }
+
+    #[test]
+    fn test_ranking() {
+        let (order, boards) = prepare(RAW).unwrap();
+        let standings = ranking(&order, &boards, &Lines);
+        assert_eq!(
+            Standing {
+                rank: 1,
+                board: 2,
+                win: Some(Win {
+                    turn: 11,
+                    number: 24,
+                    score: 4512
+                })
+            },
+            standings[0]
+        );
+        assert_eq!(
+            vec![2, 0, 1],
+            standings
+                .iter()
+                .map(|standing| standing.board)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            " rank board  turn drawn    score\n    1     2    11    24     4512\n    2     0    13    16     2192\n    3     1    14    13     1924",
+            ranking_table(&standings)
+        );
+
+        // The first and last card win together, the middle one is too small for the pattern
+        let (order, boards) =
+            prepare("5,1,9,3,7,2,4,6,8\n\n1 2 3\n4 5 6\n7 8 9\n\n1 2\n3 4\n\n9 8 7\n6 5 4\n3 2 1")
+                .unwrap();
+        let pair: Pattern = "x.x".parse().unwrap();
+        let standings = ranking(&order, &boards, &pair);
+        assert_eq!(
+            vec![(1, 0), (1, 2), (3, 1)],
+            standings
+                .iter()
+                .map(|standing| (standing.rank, standing.board))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(None, standings[2].win);
+        assert_eq!(
+            bingo_winner(&order, &boards, &pair),
+            standings[0].win.map(|win| win.score)
+        );
+        assert_eq!(
+            bingo_loser(&order, &boards, &pair),
+            standings[1].win.map(|win| win.score)
+        );
+        assert_eq!(
+            "   =1     0     3     3       81\n   =1     2     3     3       81\n    -     1 never wins",
+            ranking_table(&standings).split_once('\n').unwrap().1
+        );
+        assert_eq!(None, bingo_winner(&order, &boards[1..2], &pair));
+    }
+
+    #[test]
+    fn test_never_drawn() {
+        // Blank lines around and between the boards, tabs, carriage returns and 99 never drawn
+        let raw = "\n3, 1,2,4\r\n\n\n 1\t2 \r\n99  3\n\n\n\n4 1\n2 3\n\n";
+        let (order, boards) = prepare(raw).unwrap();
+        assert_eq!(vec![3, 1, 2, 4], order);
+        assert_eq!(2, boards.len());
+        assert_eq!(None, boards[0].winning_turn(order.len(), &Blackout));
+        // The second board wins on 1, the first on 2 with only 99 left unmarked
+        assert_eq!(Some(4 + 2), bingo_winner(&order, &boards, &Lines));
+        assert_eq!(Some(2 * 99), bingo_loser(&order, &boards, &Lines));
+        assert_eq!(" 1  2\n99  3\n", boards[0].to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(Err(BingoError::NoDraws), prepare("\n\n"));
+        assert_eq!(
+            Err(BingoError::Draw {
+                position: 2,
+                value: "x".to_string()
+            }),
+            prepare("1,2,x\n\n1 2\n3 4")
+        );
+        assert_eq!(Err(BingoError::NoBoards), prepare("1,2,3\n\n"));
+        assert_eq!(
+            Err(BingoError::Number {
+                board: 1,
+                row: 1,
+                col: 0,
+                value: "-3".to_string()
+            }),
+            prepare("1,2\n\n1 2\n3 4\n\n1 2\n-3 4")
+        );
+        let ragged = prepare("1,2\n\n1 2\n3 4 5");
+        assert_eq!(
+            Err(BingoError::Ragged {
+                board: 0,
+                row: 1,
+                width: 3,
+                expected: 2
+            }),
+            ragged
+        );
+        assert_eq!(
+            "Board 0 row 1 has 3 numbers instead of 2",
+            ragged.unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::days::bingo::{ranking, Board, Position, Standing, WinRule};
+use crate::theme::Theme;
+
+/// The width the boards are laid out in.
+pub const WIDTH: usize = 80;
+
+/// A game of bingo shown turn by turn, every board with the fields drawn so far marked.
+pub struct Replay<'a> {
+    order: &'a [u32],
+    boards: &'a [Board],
+    rule: &'a dyn WinRule,
+    standings: Vec<Standing>,
+}
+
+impl<'a> Replay<'a> {
+    pub fn new(order: &'a [u32], boards: &'a [Board], rule: &'a dyn WinRule) -> Self {
+        Self {
+            order,
+            boards,
+            rule,
+            standings: ranking(order, boards, rule),
+        }
+    }
+
+    /// The turns worth showing, up to the one the last board wins on.
+    pub fn turns(&self) -> usize {
+        self.standings
+            .iter()
+            .filter_map(|standing| standing.win)
+            .map(|win| win.turn + 1)
+            .max()
+            .filter(|_| self.standings.iter().all(|standing| standing.win.is_some()))
+            .unwrap_or(self.order.len())
+    }
+
+    /// The fields `board` won with, once it has won by `turn`.
+    fn winning_fields(&self, board: usize, turn: usize) -> Vec<Position> {
+        let standing = self
+            .standings
+            .iter()
+            .find(|standing| standing.board == board);
+        match standing.and_then(|standing| standing.win) {
+            Some(win) if win.turn <= turn => self
+                .rule
+                .winning_fields(&self.boards[board], win.turn)
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// The title over a board, saying so once it has won.
+    fn title(board: usize, won: bool) -> String {
+        match won {
+            false => format!("board {}", board),
+            true => format!("board {} won", board),
+        }
+    }
+
+    /// A board as lines of text `column` characters wide, marked fields in the `marked` color and
+    /// the fields it won with in bold `winning`. Without colors they are prefixed by `*` and `#`.
+    fn board(
+        &self,
+        board: usize,
+        turn: usize,
+        digits: usize,
+        column: usize,
+        theme: &Theme,
+    ) -> Vec<String> {
+        let card = &self.boards[board];
+        let winning = self.winning_fields(board, turn);
+        // The rows are colored, so they are padded by their width without the colors
+        let padding = " ".repeat(column - (card.width() * (digits + 2) - 1));
+        let title = Self::title(board, !winning.is_empty());
+        let mut lines = vec![format!("{:<column$}", title, column = column)];
+        lines.extend((0..card.height()).map(|row| {
+            (0..card.width())
+                .map(|col| {
+                    let number = card.number(row, col);
+                    let marker = if winning.contains(&(row, col)) {
+                        "#"
+                    } else if card.marked(row, col, turn) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    let shown = if theme.colored() { "" } else { marker };
+                    let text = format!(
+                        "{:>width$}",
+                        format!("{}{}", shown, number),
+                        width = digits + 1
+                    );
+                    match marker {
+                        "#" => theme.paint_bold(&text, theme.palette.winning).to_string(),
+                        "*" => theme.paint(&text, theme.palette.marked).to_string(),
+                        _ => text,
+                    }
+                })
+                .collect::<Vec<String>>()
+                .join(" ")
+                + &padding
+        }));
+        lines
+    }
+
+    /// The boards after draw `turn`, as many side by side as fit in `width`, followed by a banner
+    /// for every board winning on this turn.
+    pub fn frame(&self, turn: usize, width: usize, theme: &Theme) -> String {
+        let mut lines = vec![format!(
+            "Turn {} of {}: {} is drawn",
+            turn + 1,
+            self.order.len(),
+            self.order[turn]
+        )];
+        let digits = self
+            .boards
+            .iter()
+            .flat_map(Board::numbers)
+            .chain(self.order.iter().copied())
+            .map(|number| number.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let column = self
+            .boards
+            .iter()
+            .enumerate()
+            .map(|(index, board)| {
+                (board.width() * (digits + 2) - 1).max(Self::title(index, true).len())
+            })
+            .max()
+            .unwrap_or(0);
+        let per_line = ((width + 2) / (column + 2)).max(1);
+
+        let blocks: Vec<Vec<String>> = (0..self.boards.len())
+            .map(|board| self.board(board, turn, digits, column, theme))
+            .collect();
+        for group in blocks.chunks(per_line) {
+            lines.push(String::new());
+            let height = group.iter().map(Vec::len).max().unwrap_or(0);
+            lines.extend((0..height).map(|row| {
+                group
+                    .iter()
+                    .map(|block| match block.get(row) {
+                        Some(line) => line.clone(),
+                        None => " ".repeat(column),
+                    })
+                    .collect::<Vec<String>>()
+                    .join("  ")
+                    .trim_end()
+                    .to_string()
+            }));
+        }
+
+        let last = self
+            .standings
+            .iter()
+            .rev()
+            .find(|standing| standing.win.is_some())
+            .map(|standing| standing.board);
+        for standing in &self.standings {
+            if let Some(win) = standing.win.filter(|win| win.turn == turn) {
+                let banner = if standing.rank == 1 {
+                    format!("Board {} wins first, score {}", standing.board, win.score)
+                } else if Some(standing.board) == last {
+                    format!("Board {} wins last, score {}", standing.board, win.score)
+                } else {
+                    format!("Board {} wins, score {}", standing.board, win.score)
+                };
+                lines.push(String::new());
+                lines.push(theme.paint_bold(&banner, theme.palette.winning).to_string());
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::bingo::{prepare, Lines};
+    use crate::days::bingo_replay::Replay;
+    use crate::theme::Theme;
+
+    const RAW: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
+
+22 13 17 11  0
+ 8  2 23  4 24
+21  9 14 16  7
+ 6 10  3 18  5
+ 1 12 20 15 19
+
+ 3 15  0  2 22
+ 9 18 13 17  5
+19  8  7 25 23
+20 11 10 24  4
+14 21 16 12  6
+
+14 21 17 24  4
+10 16 15  9 19
+18  8 23 26 20
+22 11 13  6  5
+ 2  0 12  3  7";
+
+    #[test]
+    fn test_replay() {
+        let (order, boards) = prepare(RAW).unwrap();
+        let replay = Replay::new(&order, &boards, &Lines);
+        assert_eq!(15, replay.turns());
+
+        let frame = replay.frame(11, 80, &Theme::plain());
+        let lines: Vec<&str> = frame.lines().collect();
+        assert_eq!("Turn 12 of 27: 24 is drawn", lines[0]);
+        assert_eq!(
+            "board 0              board 1              board 2 won",
+            lines[2]
+        );
+        assert_eq!(
+            " 22  13 *17 *11  *0    3  15  *0  *2  22  #14 #21 #17 #24  #4",
+            lines[3]
+        );
+        assert_eq!(
+            "  1  12  20  15  19  *14 *21  16  12   6   *2  *0  12   3  *7",
+            lines[7]
+        );
+        // Only two boards fit side by side
+        let narrow = replay.frame(11, 40, &Theme::plain());
+        assert_eq!("board 2 won", narrow.lines().nth(9).unwrap());
+        assert_eq!("Board 2 wins first, score 4512", lines[lines.len() - 1]);
+    }
+
+    #[test]
+    fn test_narrow_boards() {
+        let (order, boards) = prepare("1,2,3,4,5\n\n1 2\n\n3\n4\n\n5 4").unwrap();
+        let replay = Replay::new(&order, &boards, &Lines);
+        let frame = replay.frame(1, 80, &Theme::plain());
+        let lines: Vec<&str> = frame.lines().collect();
+        // Every column is as wide as the longest title, as the cards are narrower, and board 0
+        // won with its first column
+        assert_eq!("board 0 won  board 1      board 2", lines[2]);
+        assert_eq!("#1 *2         3            5  4", lines[3]);
+        assert_eq!("              4", lines[4]);
+    }
+}
@@ -0,0 +1,480 @@
+use crate::bignum::Word;
+use crate::params::Parameter;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static PARAMETERS: [Parameter; 1] = [Parameter::choice(
+    "ties",
+    "the bit counted as most common when ones and zeros are as common",
+    &["one", "zero", "error"],
+    ["one", "one"],
+)];
+
+/// How a bit position with as many ones as zeros is treated, by both parts.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum TiePolicy {
+    #[default]
+    One,
+    Zero,
+    Error,
+}
+
+impl FromStr for TiePolicy {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "one" => Ok(TiePolicy::One),
+            "zero" => Ok(TiePolicy::Zero),
+            "error" => Ok(TiePolicy::Error),
+            _ => Err("Not one of: [one, zero, error]"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DiagnosticError {
+    Tie {
+        bit: usize,
+    },
+    Empty,
+    /// Line `index` is `width` bits wide, the report before it `expected`.
+    Width {
+        index: usize,
+        width: usize,
+        expected: usize,
+    },
+    /// Line `index` holds `character` at `column` instead of a bit.
+    Character {
+        index: usize,
+        column: usize,
+        character: char,
+    },
+    /// Lines `first` and `second`, counting from 0, are the same.
+    Duplicate {
+        line: String,
+        first: usize,
+        second: usize,
+    },
+    /// Filtering got stuck at `bit` with these lines left, none or several.
+    NoUniqueRating {
+        bit: usize,
+        candidates: Vec<String>,
+    },
+}
+
+impl Display for DiagnosticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DiagnosticError::Tie { bit } => {
+                write!(f, "Bit {} has as many ones as zeros", bit)
+            }
+            DiagnosticError::Empty => write!(f, "The report is empty"),
+            DiagnosticError::Width {
+                index,
+                width,
+                expected,
+            } => write!(
+                f,
+                "Line {} is {} bits wide instead of {}",
+                index, width, expected
+            ),
+            DiagnosticError::Character {
+                index,
+                column,
+                character,
+            } => write!(
+                f,
+                "Line {} holds {:?} at column {} instead of a bit",
+                index, character, column
+            ),
+            DiagnosticError::Duplicate {
+                line,
+                first,
+                second,
+            } => write!(f, "Lines {} and {} are both {}", first, second, line),
+            DiagnosticError::NoUniqueRating { bit, candidates } => write!(
+                f,
+                "No unique rating at bit {}, {} lines left: [{}]",
+                bit,
+                candidates.len(),
+                candidates.join(", ")
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DiagnosticError {}
+
+/// The one and zero counts to compare at `bit`, a tie settled by counting one more of the
+/// preferred bit.
+pub(crate) fn settle(
+    ones: usize,
+    zeros: usize,
+    policy: TiePolicy,
+    bit: usize,
+) -> Result<(usize, usize), DiagnosticError> {
+    match policy {
+        _ if ones != zeros => Ok((ones, zeros)),
+        TiePolicy::One => Ok((ones + 1, zeros)),
+        TiePolicy::Zero => Ok((ones, zeros + 1)),
+        TiePolicy::Error => Err(DiagnosticError::Tie { bit }),
+    }
+}
+
+/// Gamma and epsilon, with the bit positions that were ties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rates {
+    pub gamma: Word,
+    pub epsilon: Word,
+    pub ties: Vec<usize>,
+}
+
+/// The first character of line `index` that is not a bit.
+pub(crate) fn check_bits(index: usize, line: &str) -> Result<(), DiagnosticError> {
+    match line
+        .chars()
+        .enumerate()
+        .find(|(_, character)| !matches!(character, '0' | '1'))
+    {
+        Some((column, character)) => Err(DiagnosticError::Character {
+            index,
+            column,
+            character,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// What is wrong with every line of the report, at most one problem per line. Lines are expected
+/// to be as wide as the first.
+pub fn problems(input: &[&str]) -> Vec<DiagnosticError> {
+    let expected = input.first().map_or(0, |line| line.chars().count());
+    input
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            if let Err(problem) = check_bits(index, line) {
+                return Some(problem);
+            }
+            let width = line.chars().count();
+            (width != expected).then_some(DiagnosticError::Width {
+                index,
+                width,
+                expected,
+            })
+        })
+        .collect()
+}
+
+/// The width of the report, or the first problem with it.
+pub fn validate(input: &[&str]) -> Result<usize, DiagnosticError> {
+    let width = input.first().ok_or(DiagnosticError::Empty)?.chars().count();
+    match problems(input).into_iter().next() {
+        Some(problem) => Err(problem),
+        None => Ok(width),
+    }
+}
+
+/// The most common bits make up gamma, the least common ones epsilon.
+pub fn rates(input: &[&str], policy: TiePolicy) -> Result<Rates, DiagnosticError> {
+    let word_size = validate(input)?;
+
+    let mut one_count = vec![0usize; word_size];
+    for line in input.iter() {
+        for (bit, character) in line.bytes().enumerate() {
+            if character == b'1' {
+                one_count[bit] += 1;
+            }
+        }
+    }
+    count_rates(&one_count, input.len(), policy)
+}
+
+/// The rates from the number of ones at every bit of `lines` lines.
+pub(crate) fn count_rates(
+    one_count: &[usize],
+    lines: usize,
+    policy: TiePolicy,
+) -> Result<Rates, DiagnosticError> {
+    let mut gamma = Word::zero(one_count.len());
+    let mut ties = Vec::new();
+    for (bit, &count) in one_count.iter().enumerate() {
+        if count * 2 == lines {
+            ties.push(bit);
+        }
+        let (ones, zeros) = settle(count, lines - count, policy, bit)?;
+        if ones > zeros {
+            gamma.set(bit);
+        }
+    }
+
+    let epsilon = gamma.complement();
+    Ok(Rates {
+        gamma,
+        epsilon,
+        ties,
+    })
+}
+
+pub fn diagnose(input: &[&str], policy: TiePolicy) -> Result<Word, DiagnosticError> {
+    let rates = rates(input, policy)?;
+    Ok(rates.gamma * rates.epsilon)
+}
+
+pub const O2_GEN: fn(&usize, &usize) -> bool = usize::ge;
+pub const CO2_SCRUB: fn(&usize, &usize) -> bool = usize::lt;
+
+/// A rating, with the bit positions where the lines left were ties.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rating {
+    pub value: Word,
+    pub ties: Vec<usize>,
+}
+
+/// The first line that appeared before, together with where.
+pub(crate) fn find_duplicate(input: &[&str]) -> Option<DiagnosticError> {
+    let mut seen = HashMap::with_capacity(input.len());
+    input.iter().enumerate().find_map(|(second, line)| {
+        seen.insert(*line, second)
+            .map(|first| DiagnosticError::Duplicate {
+                line: line.to_string(),
+                first,
+                second,
+            })
+    })
+}
+
+/// Keeps the lines with ones at every bit where `criteria` holds for the counts of ones and zeros,
+/// the lines with zeros elsewhere, until a single line is left.
+///
+/// Lines may be of different widths, but only hold bits. Duplicate lines could never be told
+/// apart, so they are refused up front. Filtering fails when the criteria leave no lines, or lines
+/// run out of bits before one is left.
+pub fn criteria_filter(
+    input: Vec<&str>,
+    criteria: fn(&usize, &usize) -> bool,
+    policy: TiePolicy,
+) -> Result<Rating, DiagnosticError> {
+    if input.is_empty() {
+        return Err(DiagnosticError::Empty);
+    }
+    for (index, line) in input.iter().enumerate() {
+        check_bits(index, line)?;
+    }
+    if let Some(duplicate) = find_duplicate(&input) {
+        return Err(duplicate);
+    }
+    let stuck = |bit: usize, lines: &[&str]| DiagnosticError::NoUniqueRating {
+        bit,
+        candidates: lines.iter().map(|line| line.to_string()).collect(),
+    };
+
+    let mut result_list = input;
+    let mut ties = Vec::new();
+    let mut bit = 0;
+    // If list contains a single result, this is the answer
+    while result_list.len() != 1 {
+        if result_list.iter().any(|line| line.len() <= bit) {
+            return Err(stuck(bit, &result_list));
+        }
+        // Split list into 0 and 1 at index 'bit'
+        let lists: (Vec<&str>, Vec<&str>) = result_list
+            .into_iter()
+            .partition(|line| line.as_bytes()[bit] == b'0');
+        if lists.0.len() == lists.1.len() {
+            ties.push(bit);
+        }
+
+        // Choose appropriate list from result
+        let (ones, zeros) = settle(lists.1.len(), lists.0.len(), policy, bit)?;
+        let (kept, dropped) = if criteria(&ones, &zeros) {
+            (lists.1, lists.0)
+        } else {
+            (lists.0, lists.1)
+        };
+        if kept.is_empty() {
+            return Err(stuck(bit, &dropped));
+        }
+        result_list = kept;
+        bit += 1;
+    }
+
+    Ok(Rating {
+        // Turn binary string into number
+        value: result_list[0].parse().unwrap(),
+        ties,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::Word;
+    use crate::days::diagnostic::{
+        criteria_filter, diagnose, problems, rates, validate, DiagnosticError, TiePolicy,
+        CO2_SCRUB, O2_GEN,
+    };
+
+    const TEST_SET: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_diagnose() {
+        assert_eq!(Ok(Word::from(198)), diagnose(&TEST_SET, TiePolicy::One));
+    }
+
+    #[test]
+    fn test_wide_words() {
+        // Every line repeated 20 times over, 100 bits wide
+        let wide: Vec<String> = TEST_SET.iter().map(|line| line.repeat(20)).collect();
+        let wide: Vec<&str> = wide.iter().map(String::as_str).collect();
+        let rates = rates(&wide, TiePolicy::One).unwrap();
+        assert_eq!("10110".repeat(20), format!("{:b}", rates.gamma));
+        assert_eq!("01001".repeat(20), format!("{:b}", rates.epsilon));
+        assert_eq!(
+            Ok(&rates.gamma * &rates.epsilon),
+            diagnose(&wide, TiePolicy::One)
+        );
+
+        let oxygen = criteria_filter(wide.clone(), O2_GEN, TiePolicy::One).unwrap();
+        assert_eq!("10111".repeat(20), format!("{:b}", oxygen.value));
+        let co2 = criteria_filter(wide, CO2_SCRUB, TiePolicy::One).unwrap();
+        assert_eq!("01010".repeat(20), format!("{:b}", co2.value));
+    }
+
+    #[test]
+    fn test_criteria() {
+        let oxygen_generator_rating = criteria_filter(TEST_SET.to_vec(), O2_GEN, TiePolicy::One)
+            .unwrap()
+            .value;
+        let co2_scrubber_rating = criteria_filter(TEST_SET.to_vec(), CO2_SCRUB, TiePolicy::One)
+            .unwrap()
+            .value;
+        println!("Oxygen generator rating: {}", oxygen_generator_rating);
+        println!("CO² scrubber rating: {}", co2_scrubber_rating);
+        assert_eq!(
+            Word::from(230),
+            oxygen_generator_rating * co2_scrubber_rating
+        );
+    }
+
+    #[test]
+    fn test_ties() {
+        let oxygen = criteria_filter(TEST_SET.to_vec(), O2_GEN, TiePolicy::One).unwrap();
+        assert_eq!((Word::from(23), vec![4]), (oxygen.value, oxygen.ties));
+        let co2 = criteria_filter(TEST_SET.to_vec(), CO2_SCRUB, TiePolicy::One).unwrap();
+        assert_eq!((Word::from(10), vec![2]), (co2.value, co2.ties));
+
+        let oxygen = criteria_filter(TEST_SET.to_vec(), O2_GEN, TiePolicy::Zero).unwrap();
+        assert_eq!(Word::from(22), oxygen.value);
+        let co2 = criteria_filter(TEST_SET.to_vec(), CO2_SCRUB, TiePolicy::Zero).unwrap();
+        assert_eq!(Word::from(15), co2.value);
+        assert_eq!(
+            Err(DiagnosticError::Tie { bit: 4 }),
+            criteria_filter(TEST_SET.to_vec(), O2_GEN, TiePolicy::Error)
+        );
+
+        let even = ["110", "011", "100", "011"];
+        assert_eq!(vec![0, 2], rates(&even, TiePolicy::One).unwrap().ties);
+        // Gamma 111 or 010
+        assert_eq!(Ok(Word::from(0)), diagnose(&even, TiePolicy::One));
+        assert_eq!(Ok(Word::from(2 * 5)), diagnose(&even, TiePolicy::Zero));
+        assert_eq!(
+            Err(DiagnosticError::Tie { bit: 0 }),
+            diagnose(&even, TiePolicy::Error)
+        );
+        assert!(rates(&TEST_SET, TiePolicy::Error).unwrap().ties.is_empty());
+    }
+
+    #[test]
+    fn test_no_unique_rating() {
+        assert_eq!(
+            Err(DiagnosticError::Empty),
+            criteria_filter(vec![], O2_GEN, TiePolicy::One)
+        );
+        assert_eq!(Err(DiagnosticError::Empty), rates(&[], TiePolicy::One));
+        assert_eq!(
+            Err(DiagnosticError::Duplicate {
+                line: "011".to_string(),
+                first: 1,
+                second: 3
+            }),
+            criteria_filter(vec!["110", "011", "100", "011"], O2_GEN, TiePolicy::One)
+        );
+
+        // Only the least common bit is kept, and no line has a one first
+        assert_eq!(
+            Err(DiagnosticError::NoUniqueRating {
+                bit: 0,
+                candidates: vec!["01".to_string(), "00".to_string()]
+            }),
+            criteria_filter(vec!["01", "00"], CO2_SCRUB, TiePolicy::One)
+        );
+        // The lines left are only as wide as the bits filtered so far
+        assert_eq!(
+            Err(DiagnosticError::NoUniqueRating {
+                bit: 1,
+                candidates: vec!["1".to_string(), "10".to_string()]
+            }),
+            criteria_filter(vec!["1", "10", "011"], O2_GEN, TiePolicy::One)
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert_eq!(Ok(5), validate(&TEST_SET));
+        assert!(problems(&TEST_SET).is_empty());
+        assert_eq!(Err(DiagnosticError::Empty), validate(&[]));
+        assert_eq!(
+            Err(DiagnosticError::Character {
+                index: 0,
+                column: 1,
+                character: 'é'
+            }),
+            validate(&["0é1", "0110"])
+        );
+
+        let report = ["0110", "011", "01x0", "10101", "1100", "0é10"];
+        assert_eq!(
+            vec![
+                DiagnosticError::Width {
+                    index: 1,
+                    width: 3,
+                    expected: 4
+                },
+                DiagnosticError::Character {
+                    index: 2,
+                    column: 2,
+                    character: 'x'
+                },
+                DiagnosticError::Width {
+                    index: 3,
+                    width: 5,
+                    expected: 4
+                },
+                DiagnosticError::Character {
+                    index: 5,
+                    column: 1,
+                    character: 'é'
+                },
+            ],
+            problems(&report)
+        );
+        assert_eq!(
+            Err(problems(&report)[0].clone()),
+            diagnose(&report, TiePolicy::One)
+        );
+        assert_eq!(
+            "Line 2 holds 'x' at column 2 instead of a bit",
+            problems(&report)[1].to_string()
+        );
+        // Ratings take lines of any width, but only bits
+        assert_eq!(
+            Err(problems(&report)[1].clone()),
+            criteria_filter(report.to_vec(), O2_GEN, TiePolicy::One)
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::bignum::Word;
+use crate::days::diagnostic::{
+    check_bits, count_rates, find_duplicate, settle, DiagnosticError, Rates, Rating, TiePolicy,
+};
+
+/// A set of lines of the report, one bit per line.
+type LineSet = Vec<u64>;
+
+/// A report stored by column: for every bit position, the set of lines with a one there.
+///
+/// Counting the ones of a column, or of the lines still in the running for a rating, is then a
+/// matter of popcounts over 64 lines at a time, instead of looking at every character.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ColumnReport {
+    columns: Vec<LineSet>,
+    lines: usize,
+    /// The first line repeating an earlier one, which no rating can tell apart.
+    duplicate: Option<DiagnosticError>,
+}
+
+fn count(set: &[u64]) -> usize {
+    set.iter().map(|block| block.count_ones() as usize).sum()
+}
+
+impl ColumnReport {
+    /// Unlike `criteria_filter`, which takes lines of any width, every line has to be as wide as
+    /// the first, as the columns are.
+    pub fn new(input: &[&str]) -> Result<Self, DiagnosticError> {
+        let width = input.first().ok_or(DiagnosticError::Empty)?.len();
+        let blocks = input.len().div_ceil(64);
+        let mut columns = vec![vec![0u64; blocks]; width];
+        // A block of 64 lines at a time, so every column gets written once per block
+        let mut bits = vec![0u64; width];
+        for (block, chunk) in input.chunks(64).enumerate() {
+            bits.fill(0);
+            for (offset, line) in chunk.iter().enumerate() {
+                let index = block * 64 + offset;
+                if line.len() != width {
+                    // Past the bit check every character is one byte, so lengths are widths
+                    check_bits(index, line)?;
+                    return Err(DiagnosticError::Width {
+                        index,
+                        width: line.len(),
+                        expected: width,
+                    });
+                }
+                for (column, &character) in bits.iter_mut().zip(line.as_bytes()) {
+                    match character {
+                        b'0' | b'1' => *column |= ((character - b'0') as u64) << offset,
+                        _ => return Err(check_bits(index, line).unwrap_err()),
+                    }
+                }
+            }
+            for (column, &bits) in columns.iter_mut().zip(&bits) {
+                column[block] = bits;
+            }
+        }
+        Ok(Self {
+            columns,
+            lines: input.len(),
+            duplicate: find_duplicate(input),
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.columns.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines == 0
+    }
+
+    /// The number of lines with a one at every bit position.
+    pub fn one_counts(&self) -> Vec<usize> {
+        self.columns.iter().map(|column| count(column)).collect()
+    }
+
+    pub fn rates(&self, policy: TiePolicy) -> Result<Rates, DiagnosticError> {
+        count_rates(&self.one_counts(), self.lines, policy)
+    }
+
+    /// Line `index` put back together from the columns.
+    pub fn line(&self, index: usize) -> Word {
+        let mut word = Word::zero(self.width());
+        for (bit, column) in self.columns.iter().enumerate() {
+            if column[index / 64] & 1 << (index % 64) != 0 {
+                word.set(bit);
+            }
+        }
+        word
+    }
+
+    /// The indices of the lines in `set`.
+    fn members(set: &[u64]) -> impl Iterator<Item = usize> + '_ {
+        set.iter().enumerate().flat_map(|(block, &bits)| {
+            (0..64)
+                .filter(move |offset| bits & 1 << offset != 0)
+                .map(move |offset| block * 64 + offset)
+        })
+    }
+
+    /// `criteria_filter` on the columns. Narrowing down the lines at a bit position takes one
+    /// pass over its column. Reports with duplicate lines are refused, like `criteria_filter`
+    /// does.
+    pub fn rating(
+        &self,
+        criteria: fn(&usize, &usize) -> bool,
+        policy: TiePolicy,
+    ) -> Result<Rating, DiagnosticError> {
+        if let Some(duplicate) = &self.duplicate {
+            return Err(duplicate.clone());
+        }
+        let blocks = self.lines.div_ceil(64);
+        let mut candidates: LineSet = vec![!0; blocks];
+        if let Some(last) = candidates.last_mut() {
+            if !self.lines.is_multiple_of(64) {
+                *last = (1 << (self.lines % 64)) - 1;
+            }
+        }
+        let mut left = self.lines;
+        let mut ties = Vec::new();
+        let stuck = |bit: usize, set: &[u64]| DiagnosticError::NoUniqueRating {
+            bit,
+            candidates: Self::members(set)
+                .map(|index| format!("{:b}", self.line(index)))
+                .collect(),
+        };
+
+        for (bit, column) in self.columns.iter().enumerate() {
+            if left <= 1 {
+                break;
+            }
+            let ones: usize = candidates
+                .iter()
+                .zip(column)
+                .map(|(candidate, column)| (candidate & column).count_ones() as usize)
+                .sum();
+            if ones * 2 == left {
+                ties.push(bit);
+            }
+            let (settled_ones, settled_zeros) = settle(ones, left - ones, policy, bit)?;
+            let keep_ones = criteria(&settled_ones, &settled_zeros);
+            let kept = if keep_ones { ones } else { left - ones };
+            if kept == 0 {
+                return Err(stuck(bit, &candidates));
+            }
+            for (candidate, column) in candidates.iter_mut().zip(column) {
+                *candidate &= if keep_ones { *column } else { !column };
+            }
+            left = kept;
+        }
+
+        // Lines of the same width differ somewhere, so a single one is left
+        let line = Self::members(&candidates).next();
+        match line {
+            Some(line) => Ok(Rating {
+                value: self.line(line),
+                ties,
+            }),
+            None => Err(DiagnosticError::Empty),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::Word;
+    use crate::days::diagnostic::{
+        criteria_filter, rates, DiagnosticError, TiePolicy, CO2_SCRUB, O2_GEN,
+    };
+    use crate::days::diagnostic_packed::ColumnReport;
+
+    const TEST_SET: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_column_report() {
+        let report = ColumnReport::new(&TEST_SET).unwrap();
+        assert_eq!((5, 12), (report.width(), report.len()));
+        assert_eq!(vec![7, 5, 8, 7, 5], report.one_counts());
+        assert_eq!(Word::from(0b11110), report.line(1));
+        for policy in [TiePolicy::One, TiePolicy::Zero, TiePolicy::Error] {
+            assert_eq!(rates(&TEST_SET, policy), report.rates(policy));
+            for criteria in [O2_GEN, CO2_SCRUB] {
+                assert_eq!(
+                    criteria_filter(TEST_SET.to_vec(), criteria, policy),
+                    report.rating(criteria, policy)
+                );
+            }
+        }
+
+        assert_eq!(
+            Err(DiagnosticError::Width {
+                index: 1,
+                width: 4,
+                expected: 5
+            }),
+            ColumnReport::new(&["00100", "0111"])
+        );
+        assert_eq!(
+            Err(DiagnosticError::Character {
+                index: 1,
+                column: 3,
+                character: '2'
+            }),
+            ColumnReport::new(&["00100", "01121"])
+        );
+        // As long in bytes as the first line, and shorter in characters
+        assert_eq!(
+            Err(DiagnosticError::Character {
+                index: 1,
+                column: 1,
+                character: 'é'
+            }),
+            ColumnReport::new(&["00100", "0é10"])
+        );
+        assert_eq!(Err(DiagnosticError::Empty), ColumnReport::new(&[]));
+    }
+
+    #[test]
+    fn test_large_report() {
+        // More lines than fit a single block, from a linear congruential generator
+        let mut state = 12345u64;
+        let lines: Vec<String> = (0..1000)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                format!("{:030b}", state >> 34)
+            })
+            .collect();
+        let lines: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let report = ColumnReport::new(&lines).unwrap();
+        assert_eq!(rates(&lines, TiePolicy::One), report.rates(TiePolicy::One));
+        for criteria in [O2_GEN, CO2_SCRUB] {
+            assert_eq!(
+                criteria_filter(lines.clone(), criteria, TiePolicy::Zero),
+                report.rating(criteria, TiePolicy::Zero)
+            );
+        }
+
+        let duplicates = ColumnReport::new(&["101", "011", "101"]).unwrap();
+        assert_eq!(
+            Err(DiagnosticError::Duplicate {
+                line: "101".to_string(),
+                first: 0,
+                second: 2
+            }),
+            duplicates.rating(O2_GEN, TiePolicy::One)
+        );
+        // Refused even where filtering never gets to the duplicates
+        let input = ["111", "000", "001", "001"];
+        assert_eq!(
+            criteria_filter(input.to_vec(), O2_GEN, TiePolicy::One),
+            ColumnReport::new(&input)
+                .unwrap()
+                .rating(O2_GEN, TiePolicy::One)
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use crate::days::diagnostic::{validate, DiagnosticError};
+use crate::theme::Theme;
+
+/// The width of the bias bars, zeros to the left of the middle and ones to the right.
+pub const WIDTH: usize = 40;
+
+/// How often a bit position holds a one or a zero.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BitStatistics {
+    pub bit: usize,
+    pub ones: usize,
+    pub zeros: usize,
+}
+
+impl BitStatistics {
+    /// The share of ones, between 0.0 and 1.0.
+    pub fn ratio(&self) -> f64 {
+        self.ones as f64 / (self.ones + self.zeros) as f64
+    }
+
+    /// How much more common ones are than zeros, from -1.0 (only zeros) to 1.0 (only ones).
+    pub fn bias(&self) -> f64 {
+        (self.ones as f64 - self.zeros as f64) / (self.ones + self.zeros) as f64
+    }
+}
+
+/// The counts of every bit position of a valid report.
+pub fn statistics(input: &[&str]) -> Result<Vec<BitStatistics>, DiagnosticError> {
+    let width = validate(input)?;
+    let mut ones = vec![0; width];
+    for line in input {
+        for (count, character) in ones.iter_mut().zip(line.bytes()) {
+            *count += (character == b'1') as usize;
+        }
+    }
+    Ok(ones
+        .into_iter()
+        .enumerate()
+        .map(|(bit, ones)| BitStatistics {
+            bit,
+            ones,
+            zeros: input.len() - ones,
+        })
+        .collect())
+}
+
+/// A line per bit position with its counts, the share of ones and a bar `width` wide growing
+/// from the middle towards the more common bit, in the `zeros` or `ones` color. The most biased
+/// bit fills its half, so small differences still show.
+pub fn bias_chart(statistics: &[BitStatistics], width: usize, theme: &Theme) -> String {
+    let half = width / 2;
+    let bit_width = statistics.len().saturating_sub(1).to_string().len();
+    let count_width = statistics
+        .first()
+        .map_or(1, |first| (first.ones + first.zeros).to_string().len());
+    let largest = statistics
+        .iter()
+        .map(|statistics| statistics.bias().abs())
+        .fold(0.0, f64::max);
+    statistics
+        .iter()
+        .map(|statistics| {
+            let bias = statistics.bias();
+            let length = match largest {
+                0.0 => 0,
+                _ => ((bias.abs() / largest * half as f64).round() as usize).min(half),
+            };
+            let (zeros, ones) = if bias < 0.0 { (length, 0) } else { (0, length) };
+            format!(
+                "bit {:>bit_width$} {:>count_width$} zeros {}{}{}{}{} {:>count_width$} ones {:>5.1}%",
+                statistics.bit,
+                statistics.zeros,
+                " ".repeat(half - zeros),
+                theme.paint(&"█".repeat(zeros), theme.palette.zeros),
+                theme.glyphs("│"),
+                theme.paint(&"█".repeat(ones), theme.palette.ones),
+                " ".repeat(half - ones),
+                statistics.ones,
+                statistics.ratio() * 100.0,
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::diagnostic::DiagnosticError;
+    use crate::days::diagnostic_stats::{bias_chart, statistics, BitStatistics};
+    use crate::theme::Theme;
+
+    const TEST_SET: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_statistics() {
+        let statistics = statistics(&TEST_SET).unwrap();
+        assert_eq!(
+            BitStatistics {
+                bit: 1,
+                ones: 5,
+                zeros: 7
+            },
+            statistics[1]
+        );
+        assert_eq!(
+            vec![7, 5, 8, 7, 5],
+            statistics.iter().map(|bit| bit.ones).collect::<Vec<_>>()
+        );
+        assert_eq!(2.0 / 3.0, statistics[2].ratio());
+        assert_eq!(-1.0 / 6.0, statistics[1].bias());
+
+        let chart = bias_chart(&statistics, 12, &Theme::plain());
+        let lines: Vec<&str> = chart.lines().collect();
+        assert_eq!("bit 0  5 zeros       │███     7 ones  58.3%", lines[0]);
+        assert_eq!("bit 1  7 zeros    ███│        5 ones  41.7%", lines[1]);
+        assert_eq!("bit 2  4 zeros       │██████  8 ones  66.7%", lines[2]);
+    }
+
+    #[test]
+    fn test_statistics_errors() {
+        assert_eq!(
+            Err(DiagnosticError::Width {
+                index: 1,
+                width: 2,
+                expected: 3
+            }),
+            statistics(&["101", "10"])
+        );
+    }
+}
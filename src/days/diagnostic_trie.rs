@@ -0,0 +1,235 @@
+use crate::days::diagnostic::{check_bits, settle, DiagnosticError, Rating, TiePolicy};
+
+/// A node of the trie, standing for the lines starting with the bits on the way to it.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Node {
+    /// The nodes after a zero and after a one.
+    children: [Option<usize>; 2],
+    /// The number of lines below the node, itself included.
+    count: usize,
+    /// The index of the line ending here, if any.
+    line: Option<usize>,
+}
+
+/// A binary trie over the lines of a report, counting the lines below every node.
+///
+/// Filtering the lines by a criteria is a walk from the root, always into the child the criteria
+/// keeps, so any number of criteria can be asked without partitioning the report again.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trie {
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    /// Lines may be of any width. Duplicate lines are refused, like `criteria_filter` does.
+    pub fn new(input: &[&str]) -> Result<Self, DiagnosticError> {
+        if input.is_empty() {
+            return Err(DiagnosticError::Empty);
+        }
+        let mut trie = Trie {
+            nodes: vec![Node::default()],
+        };
+        for (index, line) in input.iter().enumerate() {
+            trie.insert(index, line)?;
+        }
+        Ok(trie)
+    }
+
+    fn insert(&mut self, index: usize, line: &str) -> Result<(), DiagnosticError> {
+        check_bits(index, line)?;
+        let mut path = vec![0];
+        for character in line.bytes() {
+            let child = (character - b'0') as usize;
+            let node = path[path.len() - 1];
+            let next = match self.nodes[node].children[child] {
+                Some(next) => next,
+                None => {
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children[child] = Some(self.nodes.len() - 1);
+                    self.nodes.len() - 1
+                }
+            };
+            path.push(next);
+        }
+        let end = &mut self.nodes[path[path.len() - 1]];
+        if let Some(first) = end.line {
+            return Err(DiagnosticError::Duplicate {
+                line: line.to_string(),
+                first,
+                second: index,
+            });
+        }
+        end.line = Some(index);
+        // Only counted once the line is known to be new
+        path.iter().for_each(|&node| self.nodes[node].count += 1);
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The node reached by `prefix`, if any line starts with it.
+    fn find(&self, prefix: &str) -> Option<usize> {
+        prefix
+            .bytes()
+            .try_fold(0, |node, character| match character {
+                b'0' | b'1' => self.nodes[node].children[(character - b'0') as usize],
+                _ => None,
+            })
+    }
+
+    /// The number of lines starting with `prefix`.
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.find(prefix).map_or(0, |node| self.nodes[node].count)
+    }
+
+    /// The lines below `node`, whose path is `prefix`, in the order of the report.
+    fn lines_below(&self, node: usize, prefix: &str) -> Vec<String> {
+        let mut lines = Vec::new();
+        let mut stack = vec![(node, prefix.to_string())];
+        while let Some((node, path)) = stack.pop() {
+            let Node { children, line, .. } = &self.nodes[node];
+            if let Some(index) = line {
+                lines.push((*index, path.clone()));
+            }
+            for (bit, child) in children.iter().enumerate() {
+                if let Some(child) = child {
+                    stack.push((*child, format!("{}{}", path, bit)));
+                }
+            }
+        }
+        lines.sort();
+        lines.into_iter().map(|(_, line)| line).collect()
+    }
+
+    /// `criteria_filter` as a walk down the trie, for any criteria on the counts of ones and
+    /// zeros: `O2_GEN`, `CO2_SCRUB` or a closure.
+    pub fn rating(
+        &self,
+        criteria: impl Fn(&usize, &usize) -> bool,
+        policy: TiePolicy,
+    ) -> Result<Rating, DiagnosticError> {
+        let mut node = 0;
+        let mut path = String::new();
+        let mut ties = Vec::new();
+        let count = |child: Option<usize>| child.map_or(0, |child| self.nodes[child].count);
+
+        while self.nodes[node].count > 1 {
+            let bit = path.len();
+            let [zeros, ones] = self.nodes[node].children.map(count);
+            if self.nodes[node].line.is_some() {
+                return Err(DiagnosticError::NoUniqueRating {
+                    bit,
+                    candidates: self.lines_below(node, &path),
+                });
+            }
+            if ones == zeros {
+                ties.push(bit);
+            }
+            let (settled_ones, settled_zeros) = settle(ones, zeros, policy, bit)?;
+            let kept = criteria(&settled_ones, &settled_zeros) as usize;
+            match self.nodes[node].children[kept] {
+                Some(child) => node = child,
+                None => {
+                    return Err(DiagnosticError::NoUniqueRating {
+                        bit,
+                        candidates: self.lines_below(node, &path),
+                    })
+                }
+            }
+            path.push(if kept == 1 { '1' } else { '0' });
+        }
+
+        // A single line is left, follow it to its end
+        while self.nodes[node].line.is_none() {
+            let (bit, child) = self.nodes[node]
+                .children
+                .iter()
+                .enumerate()
+                .find_map(|(bit, child)| child.map(|child| (bit, child)))
+                .unwrap();
+            path.push(if bit == 1 { '1' } else { '0' });
+            node = child;
+        }
+        Ok(Rating {
+            value: path.parse().unwrap(),
+            ties,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::Word;
+    use crate::days::diagnostic::{criteria_filter, DiagnosticError, TiePolicy, CO2_SCRUB, O2_GEN};
+    use crate::days::diagnostic_trie::Trie;
+
+    const TEST_SET: [&str; 12] = [
+        "00100", "11110", "10110", "10111", "10101", "01111", "00111", "11100", "10000", "11001",
+        "00010", "01010",
+    ];
+
+    #[test]
+    fn test_trie() {
+        let trie = Trie::new(&TEST_SET).unwrap();
+        assert_eq!(12, trie.len());
+        assert_eq!(7, trie.count_prefix("1"));
+        assert_eq!(2, trie.count_prefix("1011"));
+        assert_eq!(0, trie.count_prefix("0000"));
+        for policy in [TiePolicy::One, TiePolicy::Zero, TiePolicy::Error] {
+            for criteria in [O2_GEN, CO2_SCRUB] {
+                assert_eq!(
+                    criteria_filter(TEST_SET.to_vec(), criteria, policy),
+                    trie.rating(criteria, policy)
+                );
+            }
+        }
+
+        // Keeps the ones only when they outnumber the zeros twice over
+        let lopsided = trie
+            .rating(|ones, zeros| *ones >= 2 * zeros, TiePolicy::One)
+            .unwrap();
+        assert_eq!(Word::from(0b00111), lopsided.value);
+        let threshold = 4;
+        let few = trie
+            .rating(|ones, _| *ones <= threshold, TiePolicy::One)
+            .unwrap();
+        assert_eq!(Word::from(0b01111), few.value);
+    }
+
+    #[test]
+    fn test_trie_errors() {
+        assert_eq!(Err(DiagnosticError::Empty), Trie::new(&[]));
+        assert_eq!(
+            Err(DiagnosticError::Duplicate {
+                line: "011".to_string(),
+                first: 1,
+                second: 3
+            }),
+            Trie::new(&["110", "011", "100", "011"])
+        );
+        assert_eq!(
+            Err(DiagnosticError::Character {
+                index: 1,
+                column: 2,
+                character: '2'
+            }),
+            Trie::new(&["0111", "0121"])
+        );
+        for (input, criteria) in [
+            (vec!["01", "00"], CO2_SCRUB),
+            (vec!["1", "10", "011"], O2_GEN),
+            (vec!["10", "1", "011", "0"], O2_GEN),
+        ] {
+            assert_eq!(
+                criteria_filter(input.clone(), criteria, TiePolicy::One),
+                Trie::new(&input).unwrap().rating(criteria, TiePolicy::One)
+            );
+        }
+    }
+}
@@ -0,0 +1,430 @@
+use crate::params::Parameter;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+pub static PARAMETERS: [Parameter; 2] = [
+    Parameter::choice(
+        "surface",
+        "what happens when the submarine rises above the surface",
+        &["error", "clamp", "fly"],
+        ["error", "error"],
+    ),
+    Parameter::choice(
+        "model",
+        "how the submarine interprets its commands",
+        &["progress", "aim", "drag"],
+        ["progress", "aim"],
+    ),
+];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Direction {
+    Forward(usize),
+    Up(usize),
+    Down(usize),
+}
+
+impl FromStr for Direction {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let direction: Vec<&str> = string.split_whitespace().collect();
+        if direction.len() != 2 {
+            return Err("Not a direction;unit pair!");
+        }
+        let units = match direction[1].parse::<usize>() {
+            Ok(units) => units,
+            Err(_) => return Err("not an integer!"),
+        };
+        match direction[0] {
+            "forward" => Ok(Direction::Forward(units)),
+            "up" => Ok(Direction::Up(units)),
+            "down" => Ok(Direction::Down(units)),
+            _ => Err("Not one of: [forward, up, down]"),
+        }
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Forward(units) => write!(f, "forward {}", units),
+            Direction::Up(units) => write!(f, "up {}", units),
+            Direction::Down(units) => write!(f, "down {}", units),
+        }
+    }
+}
+
+/// What happens when a command takes the submarine above the surface.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum BreachPolicy {
+    #[default]
+    Error,
+    Clamp,
+    Fly,
+}
+
+impl FromStr for BreachPolicy {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string {
+            "error" => Ok(BreachPolicy::Error),
+            "clamp" => Ok(BreachPolicy::Clamp),
+            "fly" => Ok(BreachPolicy::Fly),
+            _ => Err("Not one of: [error, clamp, fly]"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum InvalidMove {
+    BreachedSurface { depth: i64 },
+    OutOfRange,
+}
+
+/// The first command that could not be executed, `index` counting from 0.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DiveError {
+    pub index: usize,
+    pub direction: Direction,
+    pub reason: InvalidMove,
+}
+
+impl Display for DiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.reason {
+            InvalidMove::BreachedSurface { depth } => write!(
+                f,
+                "Command {} ({}) breaches the surface, reaching depth {}",
+                self.index, self.direction, depth
+            ),
+            InvalidMove::OutOfRange => write!(
+                f,
+                "Command {} ({}) takes the submarine out of range",
+                self.index, self.direction
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DiveError {}
+
+/// Depths above the surface are negative, as is aiming upwards.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Position {
+    pub pos: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Position {
+    pub fn new(pos: i64, depth: i64, aim: i64) -> Self {
+        Position { pos, depth, aim }
+    }
+
+    /// The next position as `model` interprets `direction`, after applying `policy`.
+    pub fn follow(
+        self,
+        model: &dyn SubmarineModel,
+        direction: Direction,
+        policy: BreachPolicy,
+    ) -> Result<Self, InvalidMove> {
+        model
+            .step(self, direction)
+            .ok_or(InvalidMove::OutOfRange)
+            .and_then(|position| position.surface(policy))
+    }
+
+    fn surface(self, policy: BreachPolicy) -> Result<Self, InvalidMove> {
+        match policy {
+            _ if self.depth >= 0 => Ok(self),
+            BreachPolicy::Error => Err(InvalidMove::BreachedSurface { depth: self.depth }),
+            BreachPolicy::Clamp => Ok(Self { depth: 0, ..self }),
+            BreachPolicy::Fly => Ok(self),
+        }
+    }
+
+    pub fn product(self) -> Option<i64> {
+        self.pos.checked_mul(self.depth)
+    }
+}
+
+fn signed(units: usize) -> Option<i64> {
+    i64::try_from(units).ok()
+}
+
+/// The meaning of the commands, turning a position and a direction into the next position.
+pub trait SubmarineModel {
+    fn name(&self) -> &'static str;
+
+    /// `None` when the next position does not fit.
+    fn step(&self, position: Position, direction: Direction) -> Option<Position>;
+}
+
+/// `up` and `down` change the depth directly, the first part.
+pub struct Progress;
+
+impl SubmarineModel for Progress {
+    fn name(&self) -> &'static str {
+        "progress"
+    }
+
+    fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let Position { pos, depth, aim } = position;
+        Some(match direction {
+            Direction::Forward(units) => {
+                Position::new(pos.checked_add(signed(units)?)?, depth, aim)
+            }
+            Direction::Up(units) => Position::new(pos, depth.checked_sub(signed(units)?)?, aim),
+            Direction::Down(units) => Position::new(pos, depth.checked_add(signed(units)?)?, aim),
+        })
+    }
+}
+
+/// `up` and `down` tilt the submarine, which changes depth moving forward, the second part.
+pub struct Aim;
+
+impl SubmarineModel for Aim {
+    fn name(&self) -> &'static str {
+        "aim"
+    }
+
+    fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let Position { pos, depth, aim } = position;
+        Some(match direction {
+            Direction::Forward(units) => Position::new(
+                pos.checked_add(signed(units)?)?,
+                depth.checked_add(aim.checked_mul(signed(units)?)?)?,
+                aim,
+            ),
+            Direction::Up(units) => Position::new(pos, depth, aim.checked_sub(signed(units)?)?),
+            Direction::Down(units) => Position::new(pos, depth, aim.checked_add(signed(units)?)?),
+        })
+    }
+}
+
+/// Aims like `Aim`, but the water levels the submarine out, halving the aim after every move
+/// forward.
+pub struct Drag;
+
+impl SubmarineModel for Drag {
+    fn name(&self) -> &'static str {
+        "drag"
+    }
+
+    fn step(&self, position: Position, direction: Direction) -> Option<Position> {
+        let next = Aim.step(position, direction)?;
+        Some(match direction {
+            Direction::Forward(_) => Position {
+                aim: next.aim / 2,
+                ..next
+            },
+            _ => next,
+        })
+    }
+}
+
+pub const MODELS: [&dyn SubmarineModel; 3] = [&Progress, &Aim, &Drag];
+
+pub fn model(name: &str) -> Option<&'static dyn SubmarineModel> {
+    MODELS.into_iter().find(|model| model.name() == name)
+}
+
+/// Every position the submarine passes through, starting at the surface.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trajectory {
+    pub positions: Vec<Position>,
+}
+
+impl Trajectory {
+    pub fn end(&self) -> Position {
+        *self.positions.last().unwrap()
+    }
+
+    pub fn max_depth(&self) -> i64 {
+        self.positions
+            .iter()
+            .map(|position| position.depth)
+            .max()
+            .unwrap()
+    }
+
+    /// The highest point reached, negative when the submarine flew.
+    pub fn min_depth(&self) -> i64 {
+        self.positions
+            .iter()
+            .map(|position| position.depth)
+            .min()
+            .unwrap()
+    }
+
+    /// The length of the path through the water, in the plane of horizontal position and depth.
+    pub fn distance(&self) -> f64 {
+        self.positions
+            .windows(2)
+            .map(|pair| {
+                let horizontal = (pair[1].pos - pair[0].pos) as f64;
+                let vertical = (pair[1].depth - pair[0].depth) as f64;
+                horizontal.hypot(vertical)
+            })
+            .sum()
+    }
+}
+
+impl Display for Trajectory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let end = self.end();
+        write!(
+            f,
+            "{} commands, ending at horizontal {} and depth {} aiming {}, depths from {} to {}, \
+            travelled {:.1}",
+            self.positions.len() - 1,
+            end.pos,
+            end.depth,
+            end.aim,
+            self.min_depth(),
+            self.max_depth(),
+            self.distance()
+        )
+    }
+}
+
+/// Follows the commands as `model` interprets them, starting at the surface.
+pub fn trace(
+    input: &[Direction],
+    model: &dyn SubmarineModel,
+    policy: BreachPolicy,
+) -> Result<Trajectory, DiveError> {
+    let mut positions = Vec::with_capacity(input.len() + 1);
+    positions.push(Position::default());
+    for (index, &direction) in input.iter().enumerate() {
+        let position = positions
+            .last()
+            .unwrap()
+            .follow(model, direction, policy)
+            .map_err(|reason| DiveError {
+                index,
+                direction,
+                reason,
+            })?;
+        positions.push(position);
+    }
+    Ok(Trajectory { positions })
+}
+
+fn product(input: &[Direction], trajectory: Trajectory) -> Result<i64, DiveError> {
+    trajectory.end().product().ok_or_else(|| DiveError {
+        index: input.len() - 1,
+        direction: input[input.len() - 1],
+        reason: InvalidMove::OutOfRange,
+    })
+}
+
+pub fn trace_dive(input: &[Direction], policy: BreachPolicy) -> Result<Trajectory, DiveError> {
+    trace(input, &Progress, policy)
+}
+
+pub fn trace_aim(input: &[Direction], policy: BreachPolicy) -> Result<Trajectory, DiveError> {
+    trace(input, &Aim, policy)
+}
+
+/// The product of the final horizontal position and depth.
+pub fn navigate(
+    input: &[Direction],
+    model: &dyn SubmarineModel,
+    policy: BreachPolicy,
+) -> Result<i64, DiveError> {
+    product(input, trace(input, model, policy)?)
+}
+
+pub fn dive(input: &[Direction], policy: BreachPolicy) -> Result<i64, DiveError> {
+    navigate(input, &Progress, policy)
+}
+
+pub fn aim(input: &[Direction], policy: BreachPolicy) -> Result<i64, DiveError> {
+    navigate(input, &Aim, policy)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::dive::Direction::{Down, Forward, Up};
+    use crate::days::dive::{
+        aim, dive, model, navigate, trace_aim, BreachPolicy, Direction, DiveError, InvalidMove,
+        Position, MODELS,
+    };
+
+    const TEST_SET: [Direction; 6] = [Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2)];
+
+    #[test]
+    fn test_dive() {
+        assert_eq!(Ok(150), dive(&TEST_SET, BreachPolicy::Error));
+    }
+
+    #[test]
+    fn test_aim() {
+        assert_eq!(Ok(900), aim(&TEST_SET, BreachPolicy::Error));
+    }
+
+    #[test]
+    fn test_breach() {
+        let breaching = [Forward(2), Down(1), Up(3), Down(4)];
+        assert_eq!(
+            Err(DiveError {
+                index: 2,
+                direction: Up(3),
+                reason: InvalidMove::BreachedSurface { depth: -2 }
+            }),
+            dive(&breaching, BreachPolicy::Error)
+        );
+        assert_eq!(Ok(8), dive(&breaching, BreachPolicy::Clamp));
+        assert_eq!(Ok(4), dive(&breaching, BreachPolicy::Fly));
+
+        let climbing = [Down(1), Up(3), Forward(2), Down(5), Forward(1)];
+        assert!(aim(&climbing, BreachPolicy::Error).is_err());
+        assert_eq!(Ok(9), aim(&climbing, BreachPolicy::Clamp));
+        assert_eq!(Ok(-3), aim(&climbing, BreachPolicy::Fly));
+    }
+
+    #[test]
+    fn test_out_of_range() {
+        let huge = [Down(usize::MAX)];
+        assert_eq!(
+            InvalidMove::OutOfRange,
+            dive(&huge, BreachPolicy::Fly).unwrap_err().reason
+        );
+        let deep = [Down(i64::MAX as usize), Down(1)];
+        assert_eq!(1, dive(&deep, BreachPolicy::Fly).unwrap_err().index);
+        let far = [Forward(1 << 32), Down(1 << 32)];
+        assert_eq!(1, dive(&far, BreachPolicy::Fly).unwrap_err().index);
+    }
+
+    #[test]
+    fn test_trajectory() {
+        let trajectory = trace_aim(&TEST_SET, BreachPolicy::Error).unwrap();
+        assert_eq!(7, trajectory.positions.len());
+        assert_eq!(Position::new(15, 60, 10), trajectory.end());
+        assert_eq!((0, 60), (trajectory.min_depth(), trajectory.max_depth()));
+        assert_eq!(
+            5.0 + 40f64.hypot(8.0) + 20f64.hypot(2.0),
+            trajectory.distance()
+        );
+    }
+
+    #[test]
+    fn test_models() {
+        for builtin in MODELS {
+            assert_eq!(builtin.name(), model(builtin.name()).unwrap().name());
+        }
+        assert!(model("sail").is_none());
+        assert_eq!(
+            aim(&TEST_SET, BreachPolicy::Error),
+            navigate(&TEST_SET, model("aim").unwrap(), BreachPolicy::Error)
+        );
+        // Dives 40 aiming 5 and levels out to aim 2, then dives 14 aiming 7
+        assert_eq!(
+            Ok(15 * 54),
+            navigate(&TEST_SET, model("drag").unwrap(), BreachPolicy::Error)
+        );
+    }
+}
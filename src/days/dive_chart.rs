@@ -0,0 +1,163 @@
+use crate::canvas::Canvas;
+use crate::days::dive::{Position, Trajectory};
+use crate::theme::{Palette, Theme, ThemeColor};
+
+pub const WIDTH: usize = 80;
+pub const HEIGHT: usize = 16;
+
+/// Falling deeper, rising towards the surface, or breaking through it.
+fn course_color(palette: &Palette, from: Position, to: Position) -> Option<ThemeColor> {
+    match (from, to) {
+        _ if to.depth < 0 || from.depth < 0 => Some(palette.error),
+        _ if to.depth > from.depth => Some(palette.falling),
+        _ if to.depth < from.depth => Some(palette.rising),
+        _ => None,
+    }
+}
+
+/// Maps positions onto a `width` by `height` plane, the surface or the highest point at the top.
+struct Scale {
+    right: f64,
+    top: f64,
+    bottom: f64,
+    width: f64,
+    height: f64,
+}
+
+impl Scale {
+    fn new(trajectory: &Trajectory, width: usize, height: usize) -> Self {
+        let right = trajectory.end().pos.max(1) as f64;
+        let top = trajectory.min_depth().min(0) as f64;
+        let bottom = (trajectory.max_depth() as f64).max(top + 1.0);
+        Self {
+            right,
+            top,
+            bottom,
+            width: (width - 1) as f64,
+            height: (height - 1) as f64,
+        }
+    }
+
+    fn x(&self, position: Position) -> f64 {
+        position.pos as f64 / self.right * self.width
+    }
+
+    fn y(&self, depth: i64) -> f64 {
+        (depth as f64 - self.top) / (self.bottom - self.top) * self.height
+    }
+}
+
+/// A braille side view of the trajectory, deeper further down, with the surface as a line.
+pub fn plot(trajectory: &Trajectory, width: usize, height: usize, theme: &Theme) -> String {
+    let mut canvas = Canvas::new(width, height);
+    let (columns, rows) = canvas.dots();
+    let scale = Scale::new(trajectory, columns, rows);
+    let dot = |position: Position| {
+        (
+            scale.x(position).round() as usize,
+            scale.y(position.depth).round() as usize,
+        )
+    };
+
+    if trajectory.min_depth() < 0 {
+        let surface = scale.y(0).round() as usize;
+        for x in (0..columns).step_by(2) {
+            canvas.set(x, surface, Some(theme.palette.surface));
+        }
+    }
+    for pair in trajectory.positions.windows(2) {
+        let color = course_color(&theme.palette, pair[0], pair[1]);
+        canvas.line(dot(pair[0]), dot(pair[1]), color);
+    }
+
+    let mut lines = canvas.render(theme);
+    if let Some(top) = lines.first_mut() {
+        top.push_str(&format!(" {}", scale.top));
+    }
+    if let Some(bottom) = lines.last_mut() {
+        bottom.push_str(&format!(" {}", trajectory.max_depth()));
+    }
+    lines.join("\n")
+}
+
+/// The side view as an SVG image, marking the deepest point.
+pub fn svg(trajectory: &Trajectory, width: usize, height: usize, palette: &Palette) -> String {
+    let scale = Scale::new(trajectory, width, height);
+    let mut elements = String::new();
+
+    let surface = scale.y(0);
+    elements.push_str(&format!(
+        "  <line x1=\"0\" y1=\"{:.1}\" x2=\"{}\" y2=\"{:.1}\" stroke=\"{}\" \
+        stroke-dasharray=\"4\"/>\n",
+        surface,
+        width,
+        surface,
+        palette.surface.hex()
+    ));
+    for pair in trajectory.positions.windows(2) {
+        let color = course_color(palette, pair[0], pair[1]).unwrap_or(palette.ok);
+        elements.push_str(&format!(
+            "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\"/>\n",
+            scale.x(pair[0]),
+            scale.y(pair[0].depth),
+            scale.x(pair[1]),
+            scale.y(pair[1].depth),
+            color.hex()
+        ));
+    }
+    let deepest = trajectory
+        .positions
+        .iter()
+        .find(|position| position.depth == trajectory.max_depth())
+        .unwrap();
+    elements.push_str(&format!(
+        "  <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"4\" fill=\"{}\"><title>depth {}</title></circle>\n",
+        scale.x(*deepest),
+        scale.y(deepest.depth),
+        palette.low_point.hex(),
+        deepest.depth
+    ));
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n  \
+        <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n{}</svg>\n",
+        width,
+        height,
+        palette.background.hex(),
+        elements
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::dive::Direction::{Down, Forward, Up};
+    use crate::days::dive::{trace_aim, trace_dive, BreachPolicy, Direction};
+    use crate::days::dive_chart::{plot, svg};
+    use crate::theme::{Palette, Theme};
+
+    const TEST_SET: [Direction; 6] = [Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2)];
+
+    #[test]
+    fn test_plot() {
+        let trajectory = trace_aim(&TEST_SET, BreachPolicy::Error).unwrap();
+        let chart = plot(&trajectory, 20, 5, &Theme::plain());
+        assert_eq!(5, chart.lines().count());
+        assert!(chart.lines().next().unwrap().ends_with(" 0"));
+        assert!(chart.lines().last().unwrap().ends_with(" 60"));
+
+        let flying = trace_dive(&[Up(2), Forward(4), Down(6)], BreachPolicy::Fly).unwrap();
+        assert!(plot(&flying, 10, 4, &Theme::plain())
+            .lines()
+            .next()
+            .unwrap()
+            .ends_with(" -2"));
+    }
+
+    #[test]
+    fn test_svg() {
+        let trajectory = trace_aim(&TEST_SET, BreachPolicy::Error).unwrap();
+        let image = svg(&trajectory, 300, 100, &Palette::default());
+        assert_eq!(7, image.matches("<line").count());
+        assert!(image.contains("<title>depth 60</title>"));
+    }
+}
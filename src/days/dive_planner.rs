@@ -0,0 +1,251 @@
+use crate::days::dive::{Direction, SubmarineModel};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The most positions the aim planner looks at before giving up.
+pub const MAX_STATES: usize = 1 << 21;
+
+/// Where a plan should take the submarine, starting from the surface.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Target {
+    pub pos: usize,
+    pub depth: usize,
+}
+
+impl FromStr for Target {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let (pos, depth) = string
+            .split_once(',')
+            .ok_or("Not a horizontal,depth pair!")?;
+        match (pos.trim().parse(), depth.trim().parse()) {
+            (Ok(pos), Ok(depth)) => Ok(Target { pos, depth }),
+            _ => Err("not an integer!"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PlanError {
+    /// Only the `progress` and `aim` models can be planned for.
+    Unsupported(&'static str),
+    Unreachable,
+    /// Commands need to move at least one unit.
+    ZeroStep,
+    SearchLimit,
+    /// The positions looked at do not fit in an `i64`.
+    OutOfRange,
+}
+
+impl Display for PlanError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PlanError::Unsupported(model) => {
+                write!(f, "Cannot plan routes for the {} model", model)
+            }
+            PlanError::Unreachable => write!(f, "The target cannot be reached"),
+            PlanError::ZeroStep => write!(f, "Commands should move at least one unit"),
+            PlanError::SearchLimit => write!(
+                f,
+                "Gave up after looking at {} positions, the target is too far",
+                MAX_STATES
+            ),
+            PlanError::OutOfRange => write!(f, "The target is too far, the search left the range"),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+/// The shortest list of commands that takes the submarine from the surface to `target` as `model`
+/// interprets them, moving at most `max_step` units per command and never breaching the surface.
+pub fn plan(
+    model: &dyn SubmarineModel,
+    target: Target,
+    max_step: usize,
+) -> Result<Vec<Direction>, PlanError> {
+    if max_step == 0 {
+        return Err(PlanError::ZeroStep);
+    }
+    match model.name() {
+        "progress" => Ok(plan_progress(target, max_step)),
+        "aim" => plan_aim(target, max_step),
+        name => Err(PlanError::Unsupported(name)),
+    }
+}
+
+/// `units` in as few steps of at most `max_step` as possible.
+fn split(units: usize, max_step: usize) -> impl Iterator<Item = usize> {
+    (0..units.div_ceil(max_step)).map(move |step| max_step.min(units - step * max_step))
+}
+
+/// Every command changes either the horizontal position or the depth by at most `max_step`, so
+/// going straight down and then straight ahead is as short as it gets.
+fn plan_progress(target: Target, max_step: usize) -> Vec<Direction> {
+    split(target.depth, max_step)
+        .map(Direction::Down)
+        .chain(split(target.pos, max_step).map(Direction::Forward))
+        .collect()
+}
+
+type State = (i64, i64, i64);
+
+/// A lower bound on the commands left: the distance ahead takes its moves forward, and a move
+/// forward of `r` units changes the depth by the aim times `r`, so every aim command can add at
+/// most `max_step` times the distance ahead to the depth.
+fn remaining(state: State, target: (i64, i64), max_step: i64) -> Result<Option<i64>, PlanError> {
+    // `units` over `step` rounded up, for positive steps
+    let steps = |units: i64, step: i64| {
+        if units == 0 {
+            0
+        } else {
+            (units - 1) / step + 1
+        }
+    };
+    let (pos, depth, aim) = state;
+    let ahead = target.0 - pos;
+    let gap = aim
+        .checked_mul(ahead)
+        .and_then(|drop| target.1.checked_sub(depth)?.checked_sub(drop))
+        .and_then(i64::checked_abs)
+        .ok_or(PlanError::OutOfRange)?;
+    match ahead {
+        0 if gap == 0 => Ok(Some(0)),
+        0 => Ok(None),
+        // A reach beyond the range covers any gap in one command, as saturating it does too
+        _ => Ok(Some(
+            steps(ahead, max_step) + steps(gap, max_step.saturating_mul(ahead)),
+        )),
+    }
+}
+
+/// An A* search over horizontal position, depth and aim. Shortest routes sometimes aim upwards on
+/// the way, so there is no simple formula like for `plan_progress`.
+fn plan_aim(target: Target, max_step: usize) -> Result<Vec<Direction>, PlanError> {
+    let signed = |units: usize| i64::try_from(units).map_err(|_| PlanError::OutOfRange);
+    let goal = (signed(target.pos)?, signed(target.depth)?);
+    // Steps longer than the whole target only overshoot it
+    let max_step = signed(max_step.min(target.pos.max(target.depth).max(1)))?;
+    let start: State = (0, 0, 0);
+
+    let mut reached: HashMap<State, (i64, Option<(State, Direction)>)> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    let estimate = remaining(start, goal, max_step)?.ok_or(PlanError::Unreachable)?;
+    reached.insert(start, (0, None));
+    open.push(Reverse((estimate, estimate, start)));
+
+    while let Some(Reverse((_, left, state))) = open.pop() {
+        let (pos, depth, aim) = state;
+        if left == 0 {
+            let mut commands = Vec::new();
+            let mut state = state;
+            while let (_, Some((previous, direction))) = reached[&state] {
+                commands.push(direction);
+                state = previous;
+            }
+            commands.reverse();
+            return Ok(commands);
+        }
+        let cost = reached[&state].0 + 1;
+        for units in 1..=max_step {
+            let forward = aim
+                .checked_mul(units)
+                .and_then(|drop| depth.checked_add(drop))
+                .zip(pos.checked_add(units));
+            let moves = [
+                (
+                    Direction::Forward(units as usize),
+                    forward.map(|(depth, pos)| (pos, depth, aim)),
+                ),
+                (
+                    Direction::Down(units as usize),
+                    aim.checked_add(units).map(|aim| (pos, depth, aim)),
+                ),
+                (
+                    Direction::Up(units as usize),
+                    aim.checked_sub(units).map(|aim| (pos, depth, aim)),
+                ),
+            ];
+            for (direction, next) in moves {
+                let next = next.ok_or(PlanError::OutOfRange)?;
+                if next.0 > goal.0 || next.1 < 0 {
+                    continue;
+                }
+                let left = match remaining(next, goal, max_step)? {
+                    Some(left) => left,
+                    None => continue,
+                };
+                if reached.get(&next).is_some_and(|&(known, _)| known <= cost) {
+                    continue;
+                }
+                if reached.len() == MAX_STATES {
+                    return Err(PlanError::SearchLimit);
+                }
+                reached.insert(next, (cost, Some((state, direction))));
+                open.push(Reverse((cost + left, left, next)));
+            }
+        }
+    }
+    Err(PlanError::Unreachable)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::dive::Direction::{Down, Forward, Up};
+    use crate::days::dive::{trace, Aim, BreachPolicy, Progress, SubmarineModel, MODELS};
+    use crate::days::dive_planner::{plan, PlanError, Target};
+
+    fn round_trip(target: Target, max_step: usize) -> [usize; 2] {
+        [&Progress as &dyn SubmarineModel, &Aim].map(|model| {
+            let commands = plan(model, target, max_step).unwrap();
+            assert!(commands.iter().all(|&direction| match direction {
+                Forward(units) | Up(units) | Down(units) => (1..=max_step).contains(&units),
+            }));
+            let end = trace(&commands, model, BreachPolicy::Error).unwrap().end();
+            assert_eq!(
+                (target.pos, target.depth),
+                (end.pos as usize, end.depth as usize)
+            );
+            commands.len()
+        })
+    }
+
+    #[test]
+    fn test_plan() {
+        assert_eq!(Ok(Target { pos: 15, depth: 60 }), "15, 60".parse());
+        assert_eq!([9, 3], round_trip(Target { pos: 15, depth: 60 }, 9));
+        assert_eq!([0, 0], round_trip(Target { pos: 0, depth: 0 }, 3));
+        // Forward 1, down 3, forward 1
+        assert_eq!([2, 3], round_trip(Target { pos: 2, depth: 3 }, 9));
+        // Down 3, forward 5, up 4, forward 4: aiming upwards is sometimes shorter
+        assert_eq!([5, 4], round_trip(Target { pos: 9, depth: 11 }, 5));
+        assert_eq!([10, 5], round_trip(Target { pos: 6, depth: 31 }, 4));
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let target = Target { pos: 0, depth: 4 };
+        assert_eq!(Ok(vec![Down(3), Down(1)]), plan(&Progress, target, 3));
+        assert_eq!(Err(PlanError::Unreachable), plan(&Aim, target, 3));
+        assert_eq!(
+            Err(PlanError::Unsupported("drag")),
+            plan(MODELS[2], target, 3)
+        );
+
+        let far = Target {
+            pos: 1 << 62,
+            depth: 0,
+        };
+        assert_eq!(Err(PlanError::OutOfRange), plan(&Aim, far, 1 << 62));
+        let beyond = Target {
+            pos: usize::MAX,
+            depth: 0,
+        };
+        assert_eq!(Err(PlanError::OutOfRange), plan(&Aim, beyond, 9));
+        assert_eq!(Err(PlanError::ZeroStep), plan(&Progress, target, 0));
+        assert_eq!(Err(PlanError::ZeroStep), plan(&Aim, target, 0));
+    }
+}
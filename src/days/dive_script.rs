@@ -0,0 +1,536 @@
+use crate::days::dive::{
+    BreachPolicy, Direction, DiveError, InvalidMove, Position, SubmarineModel, Trajectory,
+};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The most commands a script may expand to, so a few nested repeats cannot exhaust the memory.
+pub const MAX_COMMANDS: usize = 1 << 20;
+
+const KEYWORDS: [&str; 6] = ["forward", "up", "down", "turn", "repeat", "macro"];
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Turn {
+    Left,
+    Right,
+}
+
+/// The way the submarine faces, `forward` moving it that way.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Heading {
+    North,
+    #[default]
+    East,
+    South,
+    West,
+}
+
+impl Heading {
+    pub fn turn(self, turn: Turn) -> Self {
+        const CLOCKWISE: [Heading; 4] =
+            [Heading::North, Heading::East, Heading::South, Heading::West];
+        let index = CLOCKWISE
+            .iter()
+            .position(|&heading| heading == self)
+            .unwrap();
+        match turn {
+            Turn::Left => CLOCKWISE[(index + 3) % 4],
+            Turn::Right => CLOCKWISE[(index + 1) % 4],
+        }
+    }
+
+    /// One step east and north.
+    pub fn offset(self) -> (i64, i64) {
+        match self {
+            Heading::North => (0, 1),
+            Heading::East => (1, 0),
+            Heading::South => (0, -1),
+            Heading::West => (-1, 0),
+        }
+    }
+}
+
+impl Display for Heading {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Heading::North => "north",
+            Heading::East => "east",
+            Heading::South => "south",
+            Heading::West => "west",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// A statement of a script, with the line it starts on.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Statement {
+    Move { line: usize, direction: Direction },
+    Turn { line: usize, turn: Turn },
+    Repeat { count: usize, body: Vec<Statement> },
+    Call { line: usize, name: String },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ScriptError {
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    TooLong,
+    /// A command on `line` could not be executed.
+    Run {
+        line: usize,
+        error: DiveError,
+    },
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptError::Syntax { line, message } => write!(f, "Line {}: {}", line, message),
+            ScriptError::TooLong => {
+                write!(f, "The script runs more than {} commands", MAX_COMMANDS)
+            }
+            ScriptError::Run { line, error } => write!(f, "Line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for ScriptError {}
+
+fn syntax<T>(line: usize, message: String) -> Result<T, ScriptError> {
+    Err(ScriptError::Syntax { line, message })
+}
+
+/// A parsed script: its macros and the statements run from the top.
+///
+/// ```text
+/// # Comments run to the end of the line
+/// macro zigzag {
+///     down 2 forward 3 up 2
+/// }
+/// repeat 4 {
+///     zigzag
+///     turn left
+/// }
+/// ```
+///
+/// Macros are defined at the top level before they are used, so they cannot recurse.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Script {
+    pub macros: BTreeMap<String, Vec<Statement>>,
+    pub body: Vec<Statement>,
+}
+
+struct Parser<'a> {
+    tokens: std::vec::IntoIter<(usize, &'a str)>,
+    macros: BTreeMap<String, Vec<Statement>>,
+    /// The macro being defined, which cannot call itself.
+    defining: Option<&'a str>,
+    last_line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<(usize, &'a str)> {
+        let token = self.tokens.next();
+        if let Some((line, _)) = token {
+            self.last_line = line;
+        }
+        token
+    }
+
+    fn expect(&mut self, what: &str) -> Result<(usize, &'a str), ScriptError> {
+        match self.next() {
+            Some(token) => Ok(token),
+            None => syntax(
+                self.last_line,
+                format!("Expected {} at the end of the script", what),
+            ),
+        }
+    }
+
+    fn count(&mut self, keyword: &str) -> Result<usize, ScriptError> {
+        let (line, token) = self.expect("a count")?;
+        match token.parse::<usize>() {
+            Ok(count) => Ok(count),
+            Err(_) => syntax(line, format!("{} needs a count, not {}", keyword, token)),
+        }
+    }
+
+    fn open(&mut self, keyword: &str) -> Result<usize, ScriptError> {
+        match self.expect("{")? {
+            (line, "{") => Ok(line),
+            (line, token) => syntax(
+                line,
+                format!("Expected {{ after {}, not {}", keyword, token),
+            ),
+        }
+    }
+
+    /// Statements up to the `}` closing a block opened on `opened`, or to the end of the script.
+    fn block(&mut self, opened: Option<usize>) -> Result<Vec<Statement>, ScriptError> {
+        let mut statements = Vec::new();
+        loop {
+            let (line, token) = match (self.next(), opened) {
+                (Some(token), _) => token,
+                (None, None) => return Ok(statements),
+                (None, Some(opened)) => {
+                    return syntax(opened, "The block opened here is never closed".to_string())
+                }
+            };
+            statements.push(match token {
+                "forward" | "up" | "down" => {
+                    let (_, units) = self.expect("a number of units")?;
+                    match format!("{} {}", token, units).parse::<Direction>() {
+                        Ok(direction) => Statement::Move { line, direction },
+                        Err(reason) => syntax(line, format!("{} {}: {}", token, units, reason))?,
+                    }
+                }
+                "turn" => match self.expect("left or right")? {
+                    (_, "left") => Statement::Turn {
+                        line,
+                        turn: Turn::Left,
+                    },
+                    (_, "right") => Statement::Turn {
+                        line,
+                        turn: Turn::Right,
+                    },
+                    (_, other) => {
+                        syntax(line, format!("Can only turn left or right, not {}", other))?
+                    }
+                },
+                "repeat" => {
+                    let count = self.count("repeat")?;
+                    let opened = self.open("repeat")?;
+                    Statement::Repeat {
+                        count,
+                        body: self.block(Some(opened))?,
+                    }
+                }
+                "macro" if opened.is_some() => syntax(
+                    line,
+                    "Macros can only be defined at the top level".to_string(),
+                )?,
+                "macro" => {
+                    self.define()?;
+                    continue;
+                }
+                "}" if opened.is_some() => return Ok(statements),
+                "}" => syntax(line, "} without a block to close".to_string())?,
+                "{" => syntax(line, "A block needs a repeat or macro in front".to_string())?,
+                name if self.macros.contains_key(name) => Statement::Call {
+                    line,
+                    name: name.to_string(),
+                },
+                name if self.defining == Some(name) => {
+                    syntax(line, format!("Macro {} cannot call itself", name))?
+                }
+                other => syntax(line, format!("Unknown command or macro: {}", other))?,
+            });
+        }
+    }
+
+    fn define(&mut self) -> Result<(), ScriptError> {
+        let (line, name) = self.expect("a macro name")?;
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid || KEYWORDS.contains(&name) {
+            return syntax(line, format!("{} cannot name a macro", name));
+        }
+        if self.macros.contains_key(name) {
+            return syntax(line, format!("Macro {} is already defined", name));
+        }
+        let opened = self.open("macro")?;
+        self.defining = Some(name);
+        let body = self.block(Some(opened))?;
+        self.defining = None;
+        self.macros.insert(name.to_string(), body);
+        Ok(())
+    }
+}
+
+impl FromStr for Script {
+    type Err = ScriptError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        // Braces need no whitespace around them
+        let code: Vec<String> = string
+            .lines()
+            .map(|line| {
+                let code = line.split('#').next().unwrap();
+                code.replace('{', " { ").replace('}', " } ")
+            })
+            .collect();
+        let tokens: Vec<(usize, &str)> = code
+            .iter()
+            .enumerate()
+            .flat_map(|(index, line)| line.split_whitespace().map(move |word| (index + 1, word)))
+            .collect();
+        let mut parser = Parser {
+            tokens: tokens.into_iter(),
+            macros: BTreeMap::new(),
+            defining: None,
+            last_line: 1,
+        };
+        let body = parser.block(None)?;
+        Ok(Script {
+            macros: parser.macros,
+            body,
+        })
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Step {
+    Move(Direction),
+    Turn(Turn),
+}
+
+impl Script {
+    /// Expands repeats and macros into the commands to run, each with its line.
+    pub fn steps(&self) -> Result<Vec<(usize, Step)>, ScriptError> {
+        // Counted before expanding, as repeating an empty body adds nothing to stop at
+        let mut sizes = BTreeMap::new();
+        if self.size(&self.body, &mut sizes) > MAX_COMMANDS {
+            return Err(ScriptError::TooLong);
+        }
+        let mut steps = Vec::new();
+        self.expand(&self.body, &mut sizes, &mut steps);
+        Ok(steps)
+    }
+
+    /// The number of commands `statements` expand to, at most `usize::MAX`. The sizes of the
+    /// macros are remembered, so chains of macros calling each other are counted once.
+    fn size<'s>(
+        &'s self,
+        statements: &'s [Statement],
+        sizes: &mut BTreeMap<&'s str, usize>,
+    ) -> usize {
+        statements
+            .iter()
+            .map(|statement| match statement {
+                Statement::Move { .. } | Statement::Turn { .. } => 1,
+                Statement::Repeat { count, body } => count.saturating_mul(self.size(body, sizes)),
+                Statement::Call { name, .. } => match sizes.get(name.as_str()) {
+                    Some(&size) => size,
+                    None => {
+                        let size = self.size(&self.macros[name], sizes);
+                        sizes.insert(name, size);
+                        size
+                    }
+                },
+            })
+            .fold(0, usize::saturating_add)
+    }
+
+    fn expand<'s>(
+        &'s self,
+        statements: &'s [Statement],
+        sizes: &mut BTreeMap<&'s str, usize>,
+        steps: &mut Vec<(usize, Step)>,
+    ) {
+        for statement in statements {
+            match statement {
+                Statement::Move { line, direction } => steps.push((*line, Step::Move(*direction))),
+                Statement::Turn { line, turn } => steps.push((*line, Step::Turn(*turn))),
+                Statement::Repeat { body, .. } if self.size(body, sizes) == 0 => {}
+                Statement::Repeat { count, body } => {
+                    for _ in 0..*count {
+                        self.expand(body, sizes, steps);
+                    }
+                }
+                Statement::Call { name, .. } => {
+                    if sizes[name.as_str()] > 0 {
+                        self.expand(&self.macros[name], sizes, steps)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs the script, moving the submarine along its heading whenever it moves forward.
+    pub fn run(
+        &self,
+        model: &dyn SubmarineModel,
+        policy: BreachPolicy,
+    ) -> Result<Course, ScriptError> {
+        let mut course = Course {
+            trajectory: Trajectory {
+                positions: vec![Position::default()],
+            },
+            track: vec![(0, 0)],
+            heading: Heading::default(),
+        };
+        for (index, (line, step)) in self.steps()?.into_iter().enumerate() {
+            let direction = match step {
+                Step::Turn(turn) => {
+                    course.heading = course.heading.turn(turn);
+                    continue;
+                }
+                Step::Move(direction) => direction,
+            };
+            let run_error = |reason| ScriptError::Run {
+                line,
+                error: DiveError {
+                    index,
+                    direction,
+                    reason,
+                },
+            };
+            let from = course.trajectory.end();
+            let to = from.follow(model, direction, policy).map_err(run_error)?;
+            let (east, north) = *course.track.last().unwrap();
+            let (x, y) = course.heading.offset();
+            let moved = to.pos - from.pos;
+            let spot = east
+                .checked_add(x * moved)
+                .zip(north.checked_add(y * moved))
+                .ok_or_else(|| run_error(InvalidMove::OutOfRange))?;
+            course.trajectory.positions.push(to);
+            course.track.push(spot);
+        }
+        Ok(course)
+    }
+}
+
+/// Where a script took the submarine.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Course {
+    /// `pos` counts the distance moved forward, whatever the heading.
+    pub trajectory: Trajectory,
+    /// The east and north coordinates of every position of the trajectory.
+    pub track: Vec<(i64, i64)>,
+    pub heading: Heading,
+}
+
+impl Display for Course {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let (east, north) = self.track.last().unwrap();
+        write!(
+            f,
+            "{}, at east {} and north {} heading {}",
+            self.trajectory, east, north, self.heading
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::dive::Direction::{Down, Forward, Up};
+    use crate::days::dive::{trace, Aim, BreachPolicy, InvalidMove, Progress, SubmarineModel};
+    use crate::days::dive_script::{Heading, Script, ScriptError, Statement, Step, Turn};
+
+    #[test]
+    fn test_parse() {
+        let script: Script = "# the example\nforward 5 down 5\nforward 8 up 3 down 8 forward 2\n"
+            .parse()
+            .unwrap();
+        let steps: Vec<Step> = script
+            .steps()
+            .unwrap()
+            .into_iter()
+            .map(|(_, step)| step)
+            .collect();
+        let directions = [Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2)];
+        assert_eq!(directions.map(Step::Move).to_vec(), steps);
+
+        let script: Script = "macro dip{down 2}\nrepeat 2 {dip forward 1}"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            vec![Statement::Move {
+                line: 1,
+                direction: Down(2)
+            }],
+            script.macros["dip"]
+        );
+        assert_eq!(4, script.steps().unwrap().len());
+        // Commands of a macro keep the line they were written on
+        let lines: Vec<usize> = script
+            .steps()
+            .unwrap()
+            .iter()
+            .map(|(line, _)| *line)
+            .collect();
+        assert_eq!(vec![1, 2, 1, 2], lines);
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = |script: &str| match script.parse::<Script>() {
+            Err(ScriptError::Syntax { line, message }) => (line, message),
+            other => panic!("{:?} parsed as {:?}", script, other),
+        };
+        assert_eq!(
+            (2, "Unknown command or macro: sideways".to_string()),
+            error("up 1\nsideways 3")
+        );
+        assert_eq!(1, error("repeat 3 {\nforward 1\n").0);
+        assert_eq!(2, error("forward 1\n}").0);
+        assert_eq!(1, error("repeat many { forward 1 }").0);
+        assert_eq!(1, error("forward -1").0);
+        assert_eq!(2, error("forward 1\nturn around").0);
+        assert_eq!(
+            (1, "Macro loop cannot call itself".to_string()),
+            error("macro loop { forward 1 loop }")
+        );
+        assert_eq!(3, error("macro a { up 1 }\n\nmacro a { up 2 }").0);
+        assert_eq!(1, error("repeat 2 { macro a { up 1 } }").0);
+        assert_eq!(1, error("macro repeat { up 1 }").0);
+
+        let huge: Script = "repeat 1024 { repeat 1024 { forward 1 down 1 } }"
+            .parse()
+            .unwrap();
+        assert_eq!(Err(ScriptError::TooLong), huge.steps());
+        let endless: Script = "repeat 18446744073709551615 { repeat 2 { forward 1 } }"
+            .parse()
+            .unwrap();
+        assert_eq!(Err(ScriptError::TooLong), endless.steps());
+
+        // Repeating nothing, however often, runs nothing
+        let empty: Script = "macro a {}
+macro b { a a }
+macro c { b b }
+            repeat 18446744073709551615 { repeat 18446744073709551615 { c } }
+up 1"
+            .parse()
+            .unwrap();
+        assert_eq!(Ok(vec![(5, Step::Move(Up(1)))]), empty.steps());
+    }
+
+    #[test]
+    fn test_run() {
+        let directions = [Forward(5), Down(5), Forward(8), Up(3), Down(8), Forward(2)];
+        let script: Script = "forward 5\ndown 5 forward 8 up 3 down 8 forward 2"
+            .parse()
+            .unwrap();
+        for model in [&Progress as &dyn SubmarineModel, &Aim] {
+            let course = script.run(model, BreachPolicy::Error).unwrap();
+            assert_eq!(
+                trace(&directions, model, BreachPolicy::Error).unwrap(),
+                course.trajectory
+            );
+            assert_eq!((15, 0), *course.track.last().unwrap());
+        }
+
+        let square: Script = "macro side { forward 3 turn right }\nrepeat 3 { side }\ndown 2"
+            .parse()
+            .unwrap();
+        let course = square.run(&Progress, BreachPolicy::Error).unwrap();
+        assert_eq!((0, -3), *course.track.last().unwrap());
+        assert_eq!(Heading::North, course.heading);
+        assert_eq!(9, course.trajectory.end().pos);
+        assert_eq!(Heading::West, Heading::North.turn(Turn::Left));
+
+        let breaching: Script = "down 1\n\nup 2".parse().unwrap();
+        match breaching.run(&Progress, BreachPolicy::Error) {
+            Err(ScriptError::Run { line, error }) => {
+                assert_eq!(3, line);
+                assert_eq!(InvalidMove::BreachedSurface { depth: -1 }, error.reason);
+            }
+            other => panic!("Breached the surface: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,200 @@
+use crate::theme::Theme;
+use std::cmp::Ordering;
+use std::ops::{Range, RangeInclusive};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug)]
+struct Point {
+    x: usize,
+    y: usize,
+}
+
+impl Point {
+    fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl FromStr for Point {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string
+            .split_once(',')
+            .map(|(x, y)| (x.trim().parse(), y.trim().parse()))
+        {
+            Some((Ok(x), Ok(y))) => Ok(Point::new(x, y)),
+            _ => Err("Please pass two numbers separated by a comma!"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Line {
+    start: Point,
+    end: Point,
+}
+
+impl Line {
+    fn new(start: Point, end: Point) -> Self {
+        Line { start, end }
+    }
+
+    pub fn is_vertical(&self) -> bool {
+        self.start.x == self.end.x
+    }
+
+    pub fn is_horizontal(&self) -> bool {
+        self.start.y == self.end.y
+    }
+
+    fn length(&self) -> usize {
+        if self.is_vertical() {
+            self.start.y.abs_diff(self.end.y)
+        } else {
+            self.start.x.abs_diff(self.end.x)
+        }
+    }
+
+    fn generate_1d_points(start: usize, end: usize, length: usize) -> Vec<isize> {
+        match start.cmp(&end) {
+            Ordering::Less => RangeInclusive::new(start as isize, end as isize).collect(),
+            Ordering::Greater => RangeInclusive::new(end as isize, start as isize)
+                .rev()
+                .collect(),
+            Ordering::Equal => vec![start as isize; length + 1],
+        }
+    }
+
+    fn points(&self) -> Vec<Point> {
+        let length = self.length();
+        let x_s: Vec<isize> = Self::generate_1d_points(self.start.x, self.end.x, length);
+        let y_s: Vec<isize> = Self::generate_1d_points(self.start.y, self.end.y, length);
+        x_s.into_iter()
+            .zip(y_s)
+            .map(|(x, y)| Point::new(x as usize, y as usize))
+            .collect()
+    }
+}
+
+impl From<Line> for Range<Point> {
+    fn from(line: Line) -> Self {
+        line.start..line.end
+    }
+}
+
+impl FromStr for Line {
+    type Err = &'static str;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        match string
+            .split_once("->")
+            .map(|(start, end)| (start.trim().parse(), end.trim().parse()))
+        {
+            Some((Ok(start), Ok(end))) => Ok(Line::new(start, end)),
+            _ => Err("Please pass two (comma separated) coordinates separated by a arrow!"),
+        }
+    }
+}
+
+pub fn generate_field(lines: Vec<Line>) -> Vec<Vec<usize>> {
+    let flatten: Vec<Point> = lines.iter().flat_map(|line| line.points()).collect();
+    let x_size = flatten.iter().map(|point| point.x + 1).max().unwrap_or(0);
+    let y_size = flatten.iter().map(|point| point.y + 1).max().unwrap_or(0);
+
+    let mut field = vec![vec![0usize; x_size]; y_size];
+
+    for point in lines.into_iter().flat_map(|line| line.points()) {
+        field[point.y][point.x] += 1;
+    }
+
+    field
+}
+
+pub fn render_field(field: &[Vec<usize>], theme: &Theme) -> String {
+    let mut rendering = String::new();
+    for row in field {
+        for point in row {
+            rendering.push_str(match point {
+                0 => "░",
+                1 => "▒",
+                _ => "█",
+            });
+        }
+        rendering.push('\n');
+    }
+
+    theme.glyphs(&rendering).into_owned()
+}
+
+pub fn count_intersections(field: Vec<Vec<usize>>) -> usize {
+    field
+        .into_iter()
+        .flat_map(|row| row.into_iter())
+        .filter(|count| count > &1)
+        .count()
+}
+
+pub fn input_to_lines(input: Vec<&str>) -> Result<Vec<Line>, String> {
+    if input.is_empty() {
+        return Err("There are no lines of vents".to_string());
+    }
+    input
+        .iter()
+        .map(|line| {
+            line.parse::<Line>()
+                .map_err(|reason| format!("{}: {}", line, reason))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::hydrothermal_venture::{count_intersections, generate_field, input_to_lines};
+
+    const RAW_INPUT: &str = "0,9 -> 5,9
+8,0 -> 0,8
+9,4 -> 3,4
+2,2 -> 2,1
+7,0 -> 7,4
+6,4 -> 2,0
+0,9 -> 2,9
+3,4 -> 1,4
+0,0 -> 8,8
+5,5 -> 8,2";
+
+    #[test]
+    fn test_straight_intersection_count() {
+        let lines = input_to_lines(RAW_INPUT.split('\n').collect())
+            .unwrap()
+            .into_iter()
+            .filter(|line| line.is_horizontal() || line.is_vertical())
+            .collect();
+        let field = generate_field(lines);
+        let intersections = count_intersections(field);
+
+        assert_eq!(5, intersections)
+    }
+
+    #[test]
+    fn test_all_intersection_count() {
+        let lines = input_to_lines(RAW_INPUT.split('\n').collect()).unwrap();
+        let field = generate_field(lines);
+        let intersections = count_intersections(field);
+
+        assert_eq!(12, intersections)
+    }
+
+    #[test]
+    fn test_bad_lines() {
+        assert_eq!(
+            Err(
+                "0,9 -> 5: Please pass two (comma separated) coordinates separated by a arrow!"
+                    .to_string()
+            ),
+            input_to_lines(vec!["0,9 -> 5"]).map(|lines| lines.len())
+        );
+        assert!(input_to_lines(vec!["0,9 5,9"]).is_err());
+        assert!(input_to_lines(Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+use crate::bignum::Word;
+use crate::params::Parameter;
+use std::ops::{Index, IndexMut};
+
+pub static PARAMETERS: [Parameter; 1] = [Parameter::integer(
+    "days",
+    "number of days the lanternfish breed",
+    0,
+    100_000,
+    [80, 256],
+)];
+
+/// The number of fish per timer value, counted in words as they outgrow any integer in time.
+#[derive(Debug, Default)]
+pub struct Shoaling {
+    data: [Word; 9],
+    shift: u8,
+}
+
+impl Index<u8> for Shoaling {
+    type Output = Word;
+
+    fn index(&self, index: u8) -> &Self::Output {
+        if index > 8 {
+            panic!("Index out of bounds: {}", index);
+        } else {
+            &self.data[self.real_index(index)]
+        }
+    }
+}
+
+impl IndexMut<u8> for Shoaling {
+    fn index_mut(&mut self, index: u8) -> &mut Self::Output {
+        if index > 8 {
+            panic!("Index out of bounds: {}", index);
+        } else {
+            &mut self.data[self.real_index(index)]
+        }
+    }
+}
+
+impl Shoaling {
+    pub fn from_ages(ages: Vec<u8>) -> Self {
+        let mut counts = [0; 9];
+        for age in ages {
+            counts[age as usize] += 1;
+        }
+        Self {
+            data: counts.map(Word::from),
+            shift: 0,
+        }
+    }
+
+    pub fn fish_count(&self) -> Word {
+        self.data
+            .iter()
+            .fold(Word::default(), |count, fish| &count + fish)
+    }
+
+    fn real_index(&self, index: u8) -> usize {
+        let new_index = (index + self.shift) as usize;
+        new_index % 9
+    }
+
+    fn progress_day(self) -> Self {
+        let mut replacement = Self {
+            data: self.data,
+            shift: if self.shift == 8 { 0 } else { self.shift + 1 },
+        };
+        replacement[6] = &replacement[6] + &replacement[8];
+        replacement
+    }
+
+    pub fn progress_n_days(self, days: u64) -> Self {
+        (0..days).fold(self, |shoaling, _| shoaling.progress_day())
+    }
+}
+
+pub fn get_init_state(input: String) -> Result<Vec<u8>, String> {
+    input
+        .trim()
+        .split(',')
+        .map(|numstr| match numstr.trim().parse::<u8>() {
+            Ok(age) if age <= 8 => Ok(age),
+            _ => Err(format!("Not a timer from 0 to 8: {}", numstr)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bignum::Word;
+    use crate::days::lanternfish::{get_init_state, Shoaling};
+
+    const RAW_INPUT: &str = "3,4,3,1,2";
+
+    #[test]
+    fn test_shoaling_translation() {
+        let mut shoaling = Shoaling::default();
+
+        shoaling.data[3] = Word::from(1);
+        assert_eq!(Word::from(1), shoaling[3]);
+        shoaling = shoaling.progress_day();
+        assert_eq!(Word::from(0), shoaling[3]);
+        assert_eq!(Word::from(1), shoaling[2]);
+        shoaling = shoaling.progress_day();
+        shoaling = shoaling.progress_day();
+        assert_eq!(Word::from(1), shoaling[0]);
+        shoaling = shoaling.progress_day();
+        assert_eq!(Word::from(1), shoaling[8]);
+    }
+
+    #[test]
+    fn test_lanternfish() {
+        assert_eq!(
+            Word::from(5934),
+            Shoaling::from_ages(get_init_state(RAW_INPUT.into()).unwrap())
+                .progress_n_days(80)
+                .fish_count()
+        )
+    }
+
+    #[test]
+    fn test_takeover() {
+        assert_eq!(
+            Word::from(26984457539),
+            Shoaling::from_ages(get_init_state(RAW_INPUT.into()).unwrap())
+                .progress_n_days(256)
+                .fish_count()
+        )
+    }
+
+    #[test]
+    fn test_beyond_any_integer() {
+        let count = Shoaling::from_ages(get_init_state(RAW_INPUT.into()).unwrap())
+            .progress_n_days(1000)
+            .fish_count();
+        assert_eq!(None, count.to_u128());
+        assert_eq!(
+            count,
+            Shoaling::from_ages(get_init_state(RAW_INPUT.into()).unwrap())
+                .progress_n_days(993)
+                .fish_count()
+                + Shoaling::from_ages(get_init_state(RAW_INPUT.into()).unwrap())
+                    .progress_n_days(991)
+                    .fish_count()
+        );
+    }
+
+    #[test]
+    fn test_bad_timers() {
+        assert_eq!(Ok(vec![3, 4]), get_init_state("3,4\n".into()));
+        assert_eq!(
+            Err("Not a timer from 0 to 8: 9".to_string()),
+            get_init_state("3,9".into())
+        );
+        assert!(get_init_state("3,x".into()).is_err());
+        assert!(get_init_state(String::new()).is_err());
+    }
+}
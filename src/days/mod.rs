@@ -0,0 +1,19 @@
+pub mod bingo;
+pub mod bingo_replay;
+pub mod diagnostic;
+pub mod diagnostic_packed;
+pub mod diagnostic_stats;
+pub mod diagnostic_trie;
+pub mod dive;
+pub mod dive_chart;
+pub mod dive_planner;
+pub mod dive_script;
+pub mod hydrothermal_venture;
+pub mod lanternfish;
+pub mod seven_segment;
+pub mod smoke_basin;
+pub mod sonar_analytics;
+pub mod sonar_chart;
+pub mod sonar_sweep;
+pub mod syntax_scoring;
+pub mod whales;
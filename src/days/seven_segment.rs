@@ -0,0 +1,283 @@
+use crate::theme::{Theme, ThemeColor};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+#[derive(Clone)]
+pub struct Display {
+    map: HashMap<u8, String>,
+}
+
+trait WithInsert<K: Clone + Eq + Hash, V: Clone> {
+    fn with_insert(self, key: K, value: V) -> Self;
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> WithInsert<K, V> for HashMap<K, V> {
+    fn with_insert(self, key: K, value: V) -> Self {
+        let mut clone = self;
+        clone.insert(key, value);
+        clone
+    }
+}
+
+impl Display {
+    const KNOWN_SET: [usize; 4] = [2, 3, 4, 7];
+
+    const HORIZONTAL_SEGMENT_CHARS: [&'static str; 2] = ["─", "━"];
+    const VERTICAL_SEGMENT_CHARS: [&'static str; 2] = ["│", "┃"];
+
+    fn new() -> Self {
+        Self {
+            map: HashMap::new(),
+        }
+    }
+
+    pub fn from_notes(notes: Vec<&str>) -> Self {
+        let (mut first_iteration, second_iteration): (Vec<&str>, Vec<&str>) = notes
+            .into_iter()
+            .partition(|note| Self::KNOWN_SET.contains(&note.len()));
+        first_iteration.extend(second_iteration);
+        first_iteration.iter().fold(Self::new(), |display, note| {
+            display.process_information(note)
+        })
+    }
+
+    fn overlap(a: &str, b: &str) -> u8 {
+        b.chars().filter(|character| a.contains(*character)).count() as u8
+    }
+
+    fn decode_digit(&self, input: &str) -> Option<u8> {
+        Some(match input.len() {
+            // First iteration
+            2 => 1,
+            3 => 7,
+            4 => 4,
+            7 => 8,
+
+            // Second iteration
+            5 => {
+                if Self::overlap(input, &self.map[&7]) == 3 {
+                    3
+                } else if Self::overlap(&self.map[&4], input) == 2 {
+                    2
+                } else {
+                    5
+                }
+            }
+
+            6 => {
+                if Self::overlap(input, &self.map[&7]) != 3 {
+                    6
+                } else if Self::overlap(&self.map[&4], input) == 4 {
+                    9
+                } else {
+                    0
+                }
+            }
+
+            _ => return None,
+        })
+    }
+
+    fn process_information(self, input: &str) -> Self {
+        if let Some(result) = self.decode_digit(input) {
+            Self {
+                map: self.map.with_insert(result, input.to_string()),
+            }
+        } else {
+            self
+        }
+    }
+
+    pub fn decode_number(self, input: Vec<&str>) -> usize {
+        input
+            .iter()
+            .flat_map(|digit| self.decode_digit(digit))
+            .fold(String::new(), |string, digit| {
+                format!("{}{}", string, digit)
+            })
+            .parse::<usize>()
+            .unwrap()
+    }
+
+    fn num_to_segments(num: u8) -> [bool; 7] {
+        match num {
+            1 => [false, true, true, false, false, false, false],
+            2 => [true, true, false, true, true, false, true],
+            3 => [true, true, true, true, false, false, true],
+            4 => [false, true, true, false, false, true, true],
+            5 => [true, false, true, true, false, true, true],
+            6 => [true, false, true, true, true, true, true],
+            7 => [true, true, true, false, false, false, false],
+            8 => [true, true, true, true, true, true, true],
+            9 => [true, true, true, true, false, true, true],
+            0 => [true, true, true, true, true, true, false],
+            _ => [false; 7],
+        }
+    }
+
+    fn segment_to_character(segment: u8, status: bool, theme: &Theme, color: ThemeColor) -> String {
+        let set = match segment {
+            0 | 3 | 6 => Self::HORIZONTAL_SEGMENT_CHARS,
+            _ => Self::VERTICAL_SEGMENT_CHARS,
+        };
+        if status {
+            theme.paint(set[1], color).to_string()
+        } else {
+            theme.paint(set[0], theme.palette.segment_off).to_string()
+        }
+    }
+
+    fn zip_digits(one: String, another: String) -> String {
+        one.trim()
+            .split('\n')
+            .zip(another.trim().split('\n'))
+            .map(|(one, another)| format!("{}    {}", one, another))
+            .fold(String::new(), |a, b| a + "\n" + b.as_str())
+    }
+
+    pub fn display(number: usize, theme: &Theme, color: ThemeColor) -> String {
+        format!("{}", number)
+            .chars()
+            .flat_map(|character| character.to_digit(10).map(|digit| digit as u8))
+            .map(|digit| Self::display_digit(digit, theme, color))
+            .reduce(Self::zip_digits)
+            .unwrap()
+    }
+
+    fn display_digit(number: u8, theme: &Theme, color: ThemeColor) -> String {
+        let segments = Self::num_to_segments(number);
+        let digit = format!(
+            "\
+        ╭{0}{0}{0}{0}{0}{0}{0}{0}{0}{0}╮\n\
+        {5}{5}┏{0}{0}{0}{0}{0}{0}┓{1}{1}\n\
+        {5}{5}{5}      {1}{1}{1}\n\
+        {5}{5}{5}      {1}{1}{1}\n\
+        {5}{5}{5}      {1}{1}{1}\n\
+        {5}{5}┗{6}{6}{6}{6}{6}{6}┛{1}{1}\n\
+        ┣{6}{6}{6}{6}{6}{6}{6}{6}{6}{6}┫\n\
+        {4}{4}{4}      {2}{2}{2}\n\
+        {4}{4}{4}      {2}{2}{2}\n\
+        {4}{4}{4}      {2}{2}{2}\n\
+        {4}{4}┗{3}{3}{3}{3}{3}{3}┛{2}{2}\n\
+        ╰{3}{3}{3}{3}{3}{3}{3}{3}{3}{3}╯\n\
+        \
+        ",
+            Self::segment_to_character(0, segments[0], theme, color),
+            Self::segment_to_character(1, segments[1], theme, color),
+            Self::segment_to_character(2, segments[2], theme, color),
+            Self::segment_to_character(3, segments[3], theme, color),
+            Self::segment_to_character(4, segments[4], theme, color),
+            Self::segment_to_character(5, segments[5], theme, color),
+            Self::segment_to_character(6, segments[6], theme, color),
+        );
+        theme.glyphs(&digit).into_owned()
+    }
+}
+
+pub fn count_unique_digits(input: Vec<&str>) -> usize {
+    input
+        .iter()
+        .filter(|string| Display::KNOWN_SET.contains(&(string.len())))
+        .count()
+}
+
+/// The ten patterns noted of a display, and the four digits it shows.
+pub type Entry<'a> = (Vec<&'a str>, Vec<&'a str>);
+
+pub fn read_input_digits(input: &str) -> Result<Vec<Entry<'_>>, String> {
+    input
+        .trim()
+        .split('\n')
+        .map(|entry| {
+            let (notes, output) = entry.split_once('|').ok_or_else(|| {
+                format!("{}: Please separate the notes from the output by |", entry)
+            })?;
+            let notes: Vec<&str> = notes.split_whitespace().collect();
+            let output: Vec<&str> = output.split_whitespace().collect();
+            check_entry(&notes, &output)
+                .map_err(|reason| format!("{}: {}", entry.trim(), reason))?;
+            Ok((notes, output))
+        })
+        .collect()
+}
+
+/// Makes sure [Display::from_notes] can deduce every digit of the output.
+fn check_entry(notes: &[&str], output: &[&str]) -> Result<(), String> {
+    if notes.len() != 10 || output.len() != 4 {
+        return Err("Please pass 10 patterns, then 4 digits".to_string());
+    }
+    if let Some(pattern) = notes.iter().chain(output).find(|pattern| {
+        !(2..=7).contains(&pattern.len()) || !pattern.chars().all(|c| ('a'..='g').contains(&c))
+    }) {
+        return Err(format!(
+            "{} is not a pattern of 2 to 7 segments a to g",
+            pattern
+        ));
+    }
+    if [2, 3, 4]
+        .iter()
+        .any(|length| !notes.iter().any(|note| note.len() == *length))
+    {
+        return Err("The patterns lack a 1, 4 or 7".to_string());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::seven_segment::{count_unique_digits, read_input_digits, Display};
+
+    const TEST_SET: &str =
+        "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
+edbfga begcd cbg gc gcadebf fbgde acbgfd abcde gfcbed gfec | fcgedb cgb dgebacf gc
+fgaebd cg bdaec gdafb agbcfd gdcbef bgcad gfac gcb cdgabef | cg cg fdcagb cbg
+fbegcd cbd adcefb dageb afcb bc aefdc ecdab fgdeca fcdbega | efabcd cedba gadfec cb
+aecbfdg fbg gf bafeg dbefa fcge gcbea fcaegb dgceab fcbdga | gecf egdcabf bgf bfgea
+fgeab ca afcebg bdacfeg cfaedg gcfdb baec bfadeg bafgc acf | gebdcfa ecba ca fadegcb
+dbcfg fgd bdegcaf fgec aegbdf ecdfab fbedc dacgb gdcebf gf | cefg dcbef fcge gbcadfe
+bdfegc cbegaf gecbf dfcage bdacg ed bedf ced adcbefg gebcd | ed bcgafe cdgba cbgef
+egadfb cdbfeg cegd fecab cgb gbdefca cg fgcdab egfdb bfceg | gbdfcae bgc cg cgb
+gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce";
+
+    #[test]
+    fn test_digit_count() {
+        let entries = read_input_digits(TEST_SET).unwrap();
+        let unique_digit_count = entries
+            .into_iter()
+            .map(|(_, output)| count_unique_digits(output))
+            .sum::<usize>();
+        assert_eq!(26, unique_digit_count);
+    }
+
+    #[test]
+    fn test_digit_deduction() {
+        let entries = read_input_digits(TEST_SET).unwrap();
+        let sum: usize = entries
+            .into_iter()
+            .map(|(notes, output)| Display::from_notes(notes).decode_number(output))
+            .sum();
+        assert_eq!(61229, sum);
+    }
+
+    #[test]
+    fn test_bad_entries() {
+        assert!(
+            read_input_digits("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb")
+                .is_err()
+        );
+        assert!(read_input_digits("be cfbegad | fdgacbe cefdb cefbgd gcbe").is_err());
+        assert_eq!(
+            Err("be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb x gcbe: \
+                 x is not a pattern of 2 to 7 segments a to g"
+                .to_string()),
+            read_input_digits(
+                "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb x gcbe"
+            )
+            .map(|entries| entries.len())
+        );
+        assert!(read_input_digits(
+            "bec cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe"
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,332 @@
+use crate::params::Parameter;
+use crate::theme::Theme;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::{Display, Formatter};
+
+pub static PARAMETERS: [Parameter; 2] = [
+    Parameter::integer(
+        "wall",
+        "height at which a location no longer is part of a basin",
+        1,
+        10,
+        [9, 9],
+    ),
+    Parameter::integer(
+        "basins",
+        "number of largest basins multiplied",
+        1,
+        100,
+        [3, 3],
+    ),
+];
+
+#[derive(PartialEq, Clone)]
+pub struct Point {
+    pub row: usize,
+    pub col: usize,
+    height: usize,
+}
+
+impl Point {
+    fn new(row: usize, col: usize, risk: usize) -> Self {
+        Point {
+            row,
+            col,
+            height: risk,
+        }
+    }
+
+    pub fn risk(&self) -> usize {
+        self.height + 1
+    }
+}
+
+pub struct HeightMap {
+    pub rows: Vec<Vec<usize>>,
+    pub low_points: Vec<Point>,
+}
+
+impl HeightMap {
+    const SHADES: [&'static str; 5] = ["  ", "░░", "▒▒", "▓▓", "██"];
+
+    fn cell(&self, theme: &Theme, row: usize, col: usize, height: usize) -> String {
+        let is_low_point = self.low_points.contains(&Point::new(row, col, height));
+        if theme.colored() {
+            let color = if is_low_point {
+                theme.palette.low_point
+            } else {
+                theme.gradient(
+                    theme.palette.basin_floor,
+                    theme.palette.basin_wall,
+                    height as f32 / 9.0,
+                )
+            };
+            let glyph = if is_low_point { "🭭 " } else { "██" };
+            theme
+                .paint_on(glyph, color, theme.palette.background)
+                .to_string()
+        } else if is_low_point {
+            "()".to_string()
+        } else {
+            theme.glyphs(Self::SHADES[height.min(9) / 2]).into_owned()
+        }
+    }
+
+    pub fn render(&self, theme: &Theme) -> String {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(row, cols)| {
+                cols.iter()
+                    .enumerate()
+                    .map(|(col, height)| self.cell(theme, row, col, *height))
+                    .fold(String::new(), |string, character| {
+                        string + character.as_str()
+                    })
+            })
+            .fold(String::new(), |a, b| format!("{}\n{}", a, b))
+    }
+}
+
+impl Display for HeightMap {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(Theme::current()))
+    }
+}
+
+pub fn find_low_points(input: &[Vec<usize>]) -> Vec<Point> {
+    let mut result = Vec::new();
+
+    for (row_index, row) in input.iter().enumerate() {
+        for (col, point) in row.iter().enumerate() {
+            let neighbours = [
+                (row_index.wrapping_sub(1), col),
+                (row_index + 1, col),
+                (row_index, col.wrapping_sub(1)),
+                (row_index, col + 1),
+            ];
+            let lowest = neighbours.iter().all(|(row, col)| {
+                input
+                    .get(*row)
+                    .and_then(|cols| cols.get(*col))
+                    .is_none_or(|neighbour| point < neighbour)
+            });
+
+            if lowest {
+                result.push(Point::new(row_index, col, *point));
+            }
+        }
+    }
+
+    result
+}
+
+fn trace_low_point(
+    map: &[Vec<usize>],
+    current_position: (usize, usize),
+    wall: usize,
+) -> HashSet<(usize, usize)> {
+    let current_height = map[current_position.0][current_position.1];
+    if current_height >= wall {
+        HashSet::new()
+    } else {
+        let mut result = HashSet::new();
+        result.insert(current_position);
+        if current_position.0 + 1 < map.len()
+            && map[current_position.0 + 1][current_position.1] > current_height
+        {
+            result.extend(trace_low_point(
+                map,
+                (current_position.0 + 1, current_position.1),
+                wall,
+            ));
+        }
+        if current_position.1 + 1 < map[current_position.0].len()
+            && map[current_position.0][current_position.1 + 1] > current_height
+        {
+            result.extend(trace_low_point(
+                map,
+                (current_position.0, current_position.1 + 1),
+                wall,
+            ));
+        }
+        if current_position.0 != 0
+            && map[current_position.0 - 1][current_position.1] > current_height
+        {
+            result.extend(trace_low_point(
+                map,
+                (current_position.0 - 1, current_position.1),
+                wall,
+            ))
+        }
+        if current_position.1 != 0
+            && map[current_position.0][current_position.1 - 1] > current_height
+        {
+            result.extend(trace_low_point(
+                map,
+                (current_position.0, current_position.1 - 1),
+                wall,
+            ))
+        }
+        result
+    }
+}
+
+pub fn find_basins(
+    input: &[Vec<usize>],
+    low_points: Vec<(usize, usize)>,
+    wall: usize,
+) -> BTreeMap<(usize, usize), usize> {
+    low_points
+        .into_iter()
+        .enumerate()
+        .flat_map(|(basin, low_point)| {
+            trace_low_point(input, low_point, wall)
+                .into_iter()
+                .map(move |coordinates| (coordinates, basin))
+        })
+        .collect()
+}
+
+pub fn read_input_to_map(input: &str) -> Result<Vec<Vec<usize>>, String> {
+    let rows = input
+        .trim()
+        .split('\n')
+        .map(|row| {
+            row.trim()
+                .chars()
+                .map(|character| {
+                    character
+                        .to_digit(10)
+                        .map(|digit| digit as usize)
+                        .ok_or_else(|| format!("Not a height: {}", character))
+                })
+                .collect::<Result<Vec<usize>, String>>()
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if let Some((index, row)) = rows
+        .iter()
+        .enumerate()
+        .find(|(_, row)| row.len() != rows[0].len())
+    {
+        return Err(format!(
+            "Row {} is {} heights wide instead of {}",
+            index + 1,
+            row.len(),
+            rows[0].len()
+        ));
+    }
+    if rows[0].is_empty() {
+        return Err("There are no heights".to_string());
+    }
+    Ok(rows)
+}
+
+pub fn basin_frequencies(input: BTreeMap<(usize, usize), usize>) -> HashMap<usize, usize> {
+    let mut result = HashMap::new();
+    for (_, basin) in input {
+        *result.entry(basin).or_insert(0) += 1;
+    }
+
+    result
+}
+
+pub fn largest_basins(
+    input: &[Vec<usize>],
+    low_points: &[Point],
+    count: usize,
+    wall: usize,
+) -> usize {
+    let basins = find_basins(
+        input,
+        low_points
+            .iter()
+            .map(|point| (point.row, point.col))
+            .collect(),
+        wall,
+    );
+    let basin_frequencies = basin_frequencies(basins);
+    let mut basin_sizes: Vec<usize> = basin_frequencies.values().copied().collect();
+    basin_sizes.sort_unstable();
+    basin_sizes.iter().rev().take(count).product::<usize>()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::smoke_basin::{
+        basin_frequencies, find_basins, find_low_points, read_input_to_map,
+    };
+
+    const TEST_SET: &str = "2199943210
+3987894921
+9856789892
+8767896789
+9899965678";
+
+    #[test]
+    fn test_low_points() {
+        let input = read_input_to_map(TEST_SET).unwrap();
+        let result = find_low_points(&input);
+        assert_eq!(15usize, result.iter().map(|point| point.risk()).sum());
+    }
+
+    #[test]
+    fn test_basins() {
+        let input = read_input_to_map(TEST_SET).unwrap();
+
+        let low_points = find_low_points(&input);
+        let basins = find_basins(
+            &input,
+            low_points
+                .iter()
+                .map(|point| (point.row, point.col))
+                .collect(),
+            9,
+        );
+        let mut clone = input.clone();
+        for (coordinates, basin) in basins.iter() {
+            clone[coordinates.0][coordinates.1] = *basin;
+        }
+        for row in clone.iter() {
+            for col in row {
+                print!("{}", col)
+            }
+            println!()
+        }
+        let basin_frequencies = basin_frequencies(basins);
+        let mut basin_sizes: Vec<usize> = basin_frequencies.values().copied().collect();
+        basin_sizes.sort();
+        let result = basin_sizes
+            .iter()
+            .rev()
+            .take(3)
+            .inspect(|a| println!("{}", a))
+            .product::<usize>();
+        assert_eq!(1134, result)
+    }
+
+    #[test]
+    fn test_bad_maps() {
+        assert_eq!(
+            Err("Not a height: x".to_string()),
+            read_input_to_map("219\n3x8")
+        );
+        assert_eq!(
+            Err("Row 2 is 2 heights wide instead of 3".to_string()),
+            read_input_to_map("219\n39")
+        );
+        assert!(read_input_to_map("\n").is_err());
+    }
+
+    #[test]
+    fn test_single_row() {
+        let input = read_input_to_map("2199").unwrap();
+        assert_eq!(
+            2usize,
+            find_low_points(&input)
+                .iter()
+                .map(|point| point.risk())
+                .sum()
+        );
+    }
+}
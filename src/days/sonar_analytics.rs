@@ -0,0 +1,289 @@
+use crate::days::sonar_sweep::Depth;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+pub const JUMPS: usize = 5;
+
+/// Consecutive depths, `len` of them starting at `start`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Run {
+    pub start: usize,
+    pub len: usize,
+}
+
+/// The change from the depth at `index - 1` to the one at `index`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Jump<D> {
+    pub index: usize,
+    pub from: D,
+    pub to: D,
+}
+
+impl<D: Depth> Jump<D> {
+    pub fn delta(&self) -> f64 {
+        self.to.to_f64() - self.from.to_f64()
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl Statistics {
+    pub fn of<D: Depth>(depths: &[D]) -> Self {
+        let count = depths.len() as f64;
+        let mean = depths.iter().map(|depth| depth.to_f64()).sum::<f64>() / count;
+        let variance = depths
+            .iter()
+            .map(|depth| (depth.to_f64() - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        Self { mean, variance }
+    }
+
+    pub fn deviation(&self) -> f64 {
+        self.variance.sqrt()
+    }
+}
+
+/// A depth lying more than `sigmas` standard deviations from the window of depths before it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Outlier<D> {
+    pub index: usize,
+    pub depth: D,
+    pub sigmas: f64,
+}
+
+/// Maximal runs in which every depth compares to the one before it as `ordering`. Missing
+/// readings end a run.
+pub fn runs<D: Depth>(depths: &[D], ordering: Ordering) -> Vec<Run> {
+    let mut runs: Vec<Run> = Vec::new();
+    for (index, pair) in depths.windows(2).enumerate() {
+        if pair[1].partial_cmp(&pair[0]) != Some(ordering) {
+            continue;
+        }
+        match runs.last_mut() {
+            Some(run) if run.start + run.len - 1 == index => run.len += 1,
+            _ => runs.push(Run {
+                start: index,
+                len: 2,
+            }),
+        }
+    }
+    runs
+}
+
+/// The longest run, the first one when several are as long.
+pub fn longest_run<D: Depth>(depths: &[D], ordering: Ordering) -> Option<Run> {
+    runs(depths, ordering)
+        .into_iter()
+        .rev()
+        .max_by_key(|run| run.len)
+}
+
+pub fn largest_jumps<D: Depth>(depths: &[D], count: usize) -> Vec<Jump<D>> {
+    let mut jumps: Vec<Jump<D>> = depths
+        .windows(2)
+        .enumerate()
+        .map(|(index, pair)| Jump {
+            index: index + 1,
+            from: pair[0],
+            to: pair[1],
+        })
+        .filter(|jump| !jump.delta().is_nan())
+        .collect();
+    jumps.sort_by(|a, b| b.delta().abs().total_cmp(&a.delta().abs()));
+    jumps.truncate(count);
+    jumps
+}
+
+pub fn rolling_statistics<D: Depth>(depths: &[D], window: usize) -> Vec<Statistics> {
+    depths.windows(window).map(Statistics::of).collect()
+}
+
+/// Compares every depth with the statistics of the `window` depths before it. Windows that do
+/// not vary at all, or miss a reading, have no meaningful deviation and are skipped.
+pub fn outliers<D: Depth>(depths: &[D], window: usize, sigmas: f64) -> Vec<Outlier<D>> {
+    // A window as wide as memory has no depth after it, so there is nothing to compare
+    let Some(size) = window.checked_add(1) else {
+        return Vec::new();
+    };
+    depths
+        .windows(size)
+        .enumerate()
+        .filter_map(|(start, window)| {
+            let (before, depth) = window.split_at(window.len() - 1);
+            let statistics = Statistics::of(before);
+            let distance = (depth[0].to_f64() - statistics.mean).abs() / statistics.deviation();
+            (statistics.variance > 0.0 && distance > sigmas).then_some(Outlier {
+                index: start + before.len(),
+                depth: depth[0],
+                sigmas: distance,
+            })
+        })
+        .collect()
+}
+
+pub struct Analysis<D> {
+    pub window: usize,
+    pub sigmas: f64,
+    pub longest_increase: Option<Run>,
+    pub longest_decrease: Option<Run>,
+    pub plateaus: Vec<Run>,
+    pub largest_jumps: Vec<Jump<D>>,
+    pub rolling: Vec<Statistics>,
+    pub outliers: Vec<Outlier<D>>,
+}
+
+impl<D: Depth> Analysis<D> {
+    pub fn new(depths: &[D], window: usize, sigmas: f64) -> Self {
+        assert!(window > 0, "The window should hold at least one depth");
+        Self {
+            window,
+            sigmas,
+            longest_increase: longest_run(depths, Ordering::Greater),
+            longest_decrease: longest_run(depths, Ordering::Less),
+            plateaus: runs(depths, Ordering::Equal),
+            largest_jumps: largest_jumps(depths, JUMPS),
+            rolling: rolling_statistics(depths, window),
+            outliers: outliers(depths, window, sigmas),
+        }
+    }
+}
+
+impl<D: Depth> Display for Analysis<D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let run = |run: Option<Run>| match run {
+            Some(run) => format!(
+                "{} depths, from index {} to {}",
+                run.len,
+                run.start,
+                run.start + run.len - 1
+            ),
+            None => "none".to_string(),
+        };
+        writeln!(f, "Longest increase: {}", run(self.longest_increase))?;
+        writeln!(f, "Longest decrease: {}", run(self.longest_decrease))?;
+        writeln!(
+            f,
+            "Plateaus: {}, the longest {}",
+            self.plateaus.len(),
+            run(self
+                .plateaus
+                .iter()
+                .rev()
+                .max_by_key(|run| run.len)
+                .copied())
+        )?;
+
+        writeln!(f, "Largest jumps:")?;
+        for jump in &self.largest_jumps {
+            writeln!(
+                f,
+                "    {:>+6} at index {} ({} -> {})",
+                jump.delta(),
+                jump.index,
+                jump.from,
+                jump.to
+            )?;
+        }
+
+        let rolling = || {
+            self.rolling
+                .iter()
+                .enumerate()
+                .filter(|(_, statistics)| !statistics.variance.is_nan())
+        };
+        let calmest = rolling().min_by(|(_, a), (_, b)| a.variance.total_cmp(&b.variance));
+        let roughest = rolling().max_by(|(_, a), (_, b)| a.variance.total_cmp(&b.variance));
+        for (label, statistics) in [("Calmest", calmest), ("Roughest", roughest)] {
+            if let Some((start, statistics)) = statistics {
+                writeln!(
+                    f,
+                    "{} window of {}: from index {}, mean {:.1}, variance {:.1}",
+                    label, self.window, start, statistics.mean, statistics.variance
+                )?;
+            }
+        }
+
+        write!(
+            f,
+            "Outliers beyond {} standard deviations: {}",
+            self.sigmas,
+            self.outliers.len()
+        )?;
+        for outlier in &self.outliers {
+            write!(
+                f,
+                "\n    {} at index {} ({:.1} sigma)",
+                outlier.depth, outlier.index, outlier.sigmas
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::sonar_analytics::{
+        largest_jumps, longest_run, outliers, rolling_statistics, runs, Analysis, Run, Statistics,
+    };
+    use std::cmp::Ordering;
+
+    const TEST_SET: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_runs() {
+        assert_eq!(
+            Some(Run { start: 0, len: 4 }),
+            longest_run(&TEST_SET, Ordering::Greater)
+        );
+        assert_eq!(
+            Some(Run { start: 3, len: 2 }),
+            longest_run(&TEST_SET, Ordering::Less)
+        );
+        assert_eq!(
+            vec![Run { start: 1, len: 3 }, Run { start: 5, len: 2 }],
+            runs(&[1u32, 2, 2, 2, 1, 3, 3], Ordering::Equal)
+        );
+    }
+
+    #[test]
+    fn test_largest_jumps() {
+        let jumps = largest_jumps(&TEST_SET, 2);
+        assert_eq!(
+            vec![33.0, 29.0],
+            jumps.iter().map(|jump| jump.delta()).collect::<Vec<_>>()
+        );
+        assert_eq!(6, jumps[0].index);
+    }
+
+    #[test]
+    fn test_statistics() {
+        assert_eq!(
+            Statistics {
+                mean: 2.0,
+                variance: 2.0 / 3.0
+            },
+            Statistics::of(&[1u32, 2, 3])
+        );
+        assert_eq!(8, rolling_statistics(&TEST_SET, 3).len());
+
+        let spiky = [10u32, 11, 10, 11, 10, 11, 50, 10, 11];
+        let outliers = outliers(&spiky, 4, 3.0);
+        assert_eq!(1, outliers.len());
+        assert_eq!((6, 50), (outliers[0].index, outliers[0].depth));
+        // Wider than the input, and than any memory
+        let analysis = Analysis::new(&spiky, usize::MAX, 3.0);
+        assert!(analysis.rolling.is_empty() && analysis.outliers.is_empty());
+
+        let gappy = [1.0, 2.0, f64::NAN, 3.0, 4.0, 5.0, -5.5];
+        assert_eq!(
+            Some(Run { start: 3, len: 3 }),
+            longest_run(&gappy, Ordering::Greater)
+        );
+        assert_eq!(-10.5, largest_jumps(&gappy, 1)[0].delta());
+    }
+}
@@ -0,0 +1,259 @@
+use crate::canvas::Canvas;
+use crate::days::sonar_sweep::Depth;
+use crate::theme::{Palette, Theme, ThemeColor};
+
+pub const WIDTH: usize = 80;
+pub const HEIGHT: usize = 16;
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// The mean of every window of depths, the series `filtered_sonar_sweep` compares.
+pub fn smooth<D: Depth>(depths: &[D], window: usize) -> Vec<f64> {
+    depths
+        .windows(window)
+        .map(|window| window.iter().map(|depth| depth.to_f64()).sum::<f64>() / window.len() as f64)
+        .collect()
+}
+
+/// Averages the depths into at most `columns` buckets, so long series fit a terminal.
+pub fn resample(depths: &[f64], columns: usize) -> Vec<f64> {
+    if depths.len() <= columns {
+        return depths.to_vec();
+    }
+    (0..columns)
+        .map(|column| {
+            let bucket =
+                &depths[column * depths.len() / columns..(column + 1) * depths.len() / columns];
+            bucket.iter().sum::<f64>() / bucket.len() as f64
+        })
+        .collect()
+}
+
+/// The shallowest and the deepest reading, NaN being skipped.
+fn bounds(depths: &[f64]) -> (f64, f64) {
+    let min = depths.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = depths.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    (min, max)
+}
+
+/// Where `depth` lies between the bounds, from 0 to 1. A flat series lies at the top.
+fn scale(depth: f64, (min, max): (f64, f64)) -> f64 {
+    if max > min {
+        (depth - min) / (max - min)
+    } else {
+        0.0
+    }
+}
+
+fn slope_color(palette: &Palette, previous: Option<f64>, depth: f64) -> Option<ThemeColor> {
+    match previous {
+        Some(previous) if depth > previous => Some(palette.rising),
+        Some(previous) if depth < previous => Some(palette.falling),
+        _ => None,
+    }
+}
+
+/// One bar per column, taller meaning deeper.
+pub fn sparkline<D: Depth>(depths: &[D], width: usize, theme: &Theme) -> String {
+    let depths: Vec<f64> = depths
+        .iter()
+        .filter(|depth| depth.is_reading())
+        .map(|depth| depth.to_f64())
+        .collect();
+    let depths = resample(&depths, width);
+    let bounds = bounds(&depths);
+    let mut previous = None;
+    depths
+        .iter()
+        .map(|&depth| {
+            let spark = SPARKS[(scale(depth, bounds) * 7.0).round() as usize].to_string();
+            let color = slope_color(&theme.palette, previous, depth);
+            previous = Some(depth);
+            match color {
+                Some(color) => theme.paint(&spark, color).to_string(),
+                None => theme.glyphs(&spark).into_owned(),
+            }
+        })
+        .collect()
+}
+
+/// A braille line chart of the depth profile, deeper further down, with the smoothed series
+/// drawn over it. Every character holds two by four dots.
+pub fn braille_chart<D: Depth>(
+    depths: &[D],
+    window: usize,
+    width: usize,
+    height: usize,
+    theme: &Theme,
+) -> String {
+    let raw: Vec<f64> = depths.iter().map(|depth| depth.to_f64()).collect();
+    if raw.iter().all(|depth| depth.is_nan()) {
+        return String::new();
+    }
+    let mut canvas = Canvas::new(width, height);
+    let (min, max) = bounds(&raw);
+    let (columns, rows) = canvas.dots();
+
+    let mut plot = |series: &[f64],
+                    offset: f64,
+                    colors: &dyn Fn(Option<f64>, f64) -> Option<ThemeColor>| {
+        let mut previous: Option<((usize, usize), f64)> = None;
+        for (index, &depth) in series.iter().enumerate() {
+            if depth.is_nan() {
+                previous = None;
+                continue;
+            }
+            let x = ((index as f64 + offset) / (raw.len().max(2) - 1) as f64 * (columns - 1) as f64)
+                .round() as usize;
+            let y = (scale(depth, (min, max)) * (rows - 1) as f64).round() as usize;
+            let color = colors(previous.map(|(_, previous)| previous), depth);
+            canvas.line(previous.map_or((x, y), |(dot, _)| dot), (x, y), color);
+            previous = Some(((x, y), depth));
+        }
+    };
+    let palette = &theme.palette;
+    plot(&raw, 0.0, &|previous, depth| {
+        slope_color(palette, previous, depth)
+    });
+    let smoothed = smooth(depths, window);
+    plot(&smoothed, (window - 1) as f64 / 2.0, &|_, _| {
+        Some(palette.smoothed)
+    });
+
+    let mut lines = canvas.render(theme);
+    if let Some(top) = lines.first_mut() {
+        top.push_str(&format!(" {}", min));
+    }
+    if let Some(bottom) = lines.last_mut() {
+        bottom.push_str(&format!(" {}", max));
+    }
+    lines.join("\n")
+}
+
+/// The same chart as an SVG image, consecutive segments going the same way drawn as one line.
+pub fn svg<D: Depth>(
+    depths: &[D],
+    window: usize,
+    width: usize,
+    height: usize,
+    palette: &Palette,
+) -> String {
+    let raw: Vec<f64> = depths.iter().map(|depth| depth.to_f64()).collect();
+    let bounds = bounds(&raw);
+    let point = |index: f64, depth: f64| {
+        format!(
+            "{:.1},{:.1}",
+            index / (raw.len().max(2) - 1) as f64 * width as f64,
+            scale(depth, bounds) * height as f64
+        )
+    };
+    let polyline = |points: &[String], color: ThemeColor, stroke: f64| {
+        format!(
+            "  <polyline fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" points=\"{}\"/>\n",
+            color.hex(),
+            stroke,
+            points.join(" ")
+        )
+    };
+
+    let mut lines = String::new();
+    let mut segment: Vec<String> = Vec::new();
+    let mut segment_color = None;
+    for (index, pair) in raw.windows(2).enumerate() {
+        // A missing reading leaves a gap in the line
+        if pair.iter().any(|depth| depth.is_nan()) {
+            if let Some(segment_color) = segment_color.take() {
+                lines.push_str(&polyline(&segment, segment_color, 1.0));
+            }
+            continue;
+        }
+        let color = slope_color(palette, Some(pair[0]), pair[1]).unwrap_or(palette.smoothed);
+        if segment_color != Some(color) {
+            if let Some(segment_color) = segment_color {
+                lines.push_str(&polyline(&segment, segment_color, 1.0));
+            }
+            segment = vec![point(index as f64, pair[0])];
+            segment_color = Some(color);
+        }
+        segment.push(point(index as f64 + 1.0, pair[1]));
+    }
+    if let Some(segment_color) = segment_color {
+        lines.push_str(&polyline(&segment, segment_color, 1.0));
+    }
+
+    let offset = (window - 1) as f64 / 2.0;
+    let smoothed: Vec<String> = smooth(depths, window)
+        .iter()
+        .enumerate()
+        .filter(|(_, depth)| !depth.is_nan())
+        .map(|(index, &depth)| point(index as f64 + offset, depth))
+        .collect();
+    lines.push_str(&polyline(&smoothed, palette.smoothed, 2.0));
+
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\">\n  \
+        <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n{}</svg>\n",
+        width,
+        height,
+        palette.background.hex(),
+        lines
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::sonar_chart::{braille_chart, resample, smooth, sparkline, svg};
+    use crate::theme::{Palette, Theme};
+
+    const TEST_SET: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_series() {
+        assert_eq!(vec![2.0, 3.0], smooth(&[1u32, 2, 3, 4], 3));
+        assert_eq!(vec![1.5, 3.5], resample(&[1.0, 2.0, 3.0, 4.0], 2));
+        assert_eq!(vec![1.0, 2.0], resample(&[1.0, 2.0], 5));
+    }
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!("▁▁▂▂▁▂▅█▇▇", sparkline(&TEST_SET, 80, &Theme::plain()));
+    }
+
+    #[test]
+    fn test_charts() {
+        let chart = braille_chart(&TEST_SET, 3, 10, 4, &Theme::plain());
+        assert_eq!(4, chart.lines().count());
+        assert!(chart.lines().next().unwrap().ends_with(" 199"));
+        assert!(chart.lines().last().unwrap().ends_with(" 269"));
+
+        let image = svg(&TEST_SET, 3, 400, 100, &Palette::default());
+        assert!(image.starts_with("<svg"));
+        // Rising, falling and rising again, then falling and rising at the end, and the smoothed line
+        assert_eq!(6, image.matches("<polyline").count());
+
+        let gappy = [-1.5, 2.0, f64::NAN, 3.0, 0.5];
+        assert_eq!(
+            3,
+            braille_chart(&gappy, 2, 10, 3, &Theme::plain())
+                .lines()
+                .count()
+        );
+        let image = svg(&gappy, 2, 400, 100, &Palette::default());
+        assert_eq!(3, image.matches("<polyline").count());
+        assert!(!image.contains("NaN"));
+    }
+
+    #[test]
+    fn test_chart_labels() {
+        let chart = braille_chart(&[-1.5, 2.25, f64::NAN, 0.5], 1, 10, 3, &Theme::plain());
+        assert!(chart.lines().next().unwrap().ends_with(" -1.5"));
+        assert!(chart.lines().last().unwrap().ends_with(" 2.25"));
+
+        let flat = braille_chart(&[7u32, 7, 7], 1, 10, 3, &Theme::plain());
+        assert!(flat.lines().next().unwrap().ends_with(" 7"));
+        assert!(flat.lines().last().unwrap().ends_with(" 7"));
+
+        assert_eq!("", braille_chart::<u32>(&[], 3, 10, 3, &Theme::plain()));
+        assert_eq!("", braille_chart(&[f64::NAN], 1, 10, 3, &Theme::plain()));
+    }
+}
@@ -0,0 +1,219 @@
+use crate::params::Parameter;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display, Formatter};
+use std::io::BufRead;
+use std::str::FromStr;
+
+pub static PARAMETERS: [Parameter; 1] = [Parameter::integer(
+    "window",
+    "number of consecutive depths summed before comparing",
+    1,
+    1_000_000,
+    [1, 3],
+)];
+
+/// A sonar reading. Depths above sea level are negative and NaN marks a missing reading, which
+/// the sonar functions skip.
+pub trait Depth: Copy + PartialOrd + Display + Debug + FromStr {
+    fn to_f64(self) -> f64;
+
+    fn is_reading(&self) -> bool {
+        true
+    }
+}
+
+impl Depth for u32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Depth for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Depth for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn is_reading(&self) -> bool {
+        !self.is_nan()
+    }
+}
+
+/// Counts how often the sum of a sliding window of depths increases, one depth at a time.
+///
+/// Two consecutive windows share all but one depth, so the sum increases exactly when the depth
+/// entering the window is larger than the one leaving it. Only the last `window` depths are kept.
+pub struct SonarSweep<D = u32> {
+    window: usize,
+    recent: VecDeque<D>,
+    increases: usize,
+}
+
+impl<D: Depth> SonarSweep<D> {
+    pub fn new(window: usize) -> Self {
+        assert!(window > 0, "The window should hold at least one depth");
+        Self {
+            window,
+            // Grows with the depths actually read, a window wider than the input costs nothing
+            recent: VecDeque::new(),
+            increases: 0,
+        }
+    }
+
+    pub fn push(&mut self, depth: D) {
+        if !depth.is_reading() {
+            return;
+        }
+        if self.recent.len() == self.window {
+            if depth > self.recent[0] {
+                self.increases += 1;
+            }
+            self.recent.pop_front();
+        }
+        self.recent.push_back(depth);
+    }
+
+    pub fn increases(&self) -> usize {
+        self.increases
+    }
+}
+
+impl<D: Depth> Extend<D> for SonarSweep<D> {
+    fn extend<I: IntoIterator<Item = D>>(&mut self, depths: I) {
+        depths.into_iter().for_each(|depth| self.push(depth));
+    }
+}
+
+pub fn sweep<D: Depth, I: IntoIterator<Item = D>>(depths: I, window: usize) -> usize {
+    let mut sonar_sweep = SonarSweep::new(window);
+    sonar_sweep.extend(depths);
+    sonar_sweep.increases()
+}
+
+/// A line of a sonar log that could not be read, `line` counting from 1.
+#[derive(Debug, PartialEq)]
+pub enum DepthError {
+    Read { line: usize, reason: String },
+    NotADepth { line: usize, text: String },
+}
+
+impl Display for DepthError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DepthError::Read { line, reason } => write!(f, "Cannot read line {}: {}", line, reason),
+            DepthError::NotADepth { line, text } => {
+                write!(f, "Line {} is not a depth: {}", line, text)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DepthError {}
+
+/// Reads one depth per line without loading the whole input. Empty lines and `#` comments, as
+/// found in the headers of measurement files, are skipped.
+pub fn read_depths<D: Depth, R: BufRead>(reader: R) -> impl Iterator<Item = Result<D, DepthError>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            line.map(|line| (index + 1, line))
+                .map_err(|error| DepthError::Read {
+                    line: index + 1,
+                    reason: error.to_string(),
+                })
+        })
+        .filter(|line| match line {
+            Ok((_, line)) => !line.trim().is_empty() && !line.trim_start().starts_with('#'),
+            Err(_) => true,
+        })
+        .map(|line| {
+            let (line, text) = line?;
+            text.trim().parse::<D>().map_err(|_| DepthError::NotADepth {
+                line,
+                text: text.trim().to_string(),
+            })
+        })
+}
+
+pub fn sonar_sweep<D: Depth>(input: Vec<D>) -> usize {
+    sweep(input, 1)
+}
+
+pub fn filtered_sonar_sweep<D: Depth>(input: Vec<D>, window: usize) -> usize {
+    sweep(input, window)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::sonar_sweep::{
+        filtered_sonar_sweep, read_depths, sonar_sweep, sweep, DepthError,
+    };
+    use std::io::Cursor;
+
+    const TEST_SET: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_sonar_sweep() {
+        assert_eq!(7, sonar_sweep(TEST_SET.to_vec()));
+    }
+
+    #[test]
+    fn test_filtered_sonar_sweep() {
+        assert_eq!(5, filtered_sonar_sweep(TEST_SET.to_vec(), 3));
+    }
+
+    #[test]
+    fn test_streaming_sweep() {
+        for window in 1..=TEST_SET.len() + 1 {
+            let sums: Vec<u32> = TEST_SET
+                .windows(window)
+                .map(|window| window.iter().sum())
+                .collect();
+            let expected = sums.windows(2).filter(|pair| pair[1] > pair[0]).count();
+            assert_eq!(expected, sweep(TEST_SET, window));
+        }
+
+        let input = "199\n200\n208\n210\n\n200\n207\n240\n269\n260\n263\n";
+        let depths = read_depths::<u32, _>(Cursor::new(input)).map(Result::unwrap);
+        assert_eq!(5, sweep(depths, 3));
+        // Wider than the input, and than any memory
+        assert_eq!(0, sweep(TEST_SET, usize::MAX));
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let depths: Result<Vec<u32>, _> = read_depths(Cursor::new("199\n\n2OO\n")).collect();
+        assert_eq!(
+            Err(DepthError::NotADepth {
+                line: 3,
+                text: "2OO".to_string()
+            }),
+            depths
+        );
+        let invalid = Cursor::new(vec![b'1', b'\n', 0xff, b'\n']);
+        let depths: Vec<Result<u32, _>> = read_depths(invalid).collect();
+        assert_eq!(Ok(1), depths[0]);
+        assert!(matches!(depths[1], Err(DepthError::Read { line: 2, .. })));
+    }
+
+    #[test]
+    fn test_signed_and_fractional_depths() {
+        let signed: Vec<i64> = TEST_SET.iter().map(|&depth| depth as i64 - 205).collect();
+        assert_eq!(7, sonar_sweep(signed.clone()));
+        assert_eq!(5, filtered_sonar_sweep(signed, 3));
+
+        let input = "# depth (m)\n-1.5\n-0.25\nNaN\n0.5\n0.5\n2\n";
+        let depths: Vec<f64> = read_depths(Cursor::new(input))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(6, depths.len());
+        assert_eq!(3, sonar_sweep(depths.clone()));
+        assert_eq!(3, filtered_sonar_sweep(depths, 2));
+    }
+}
@@ -0,0 +1,243 @@
+use crate::theme::Theme;
+use std::collections::HashMap;
+use std::io::{stdout, Write};
+
+pub struct Stack {
+    data: Vec<char>,
+}
+
+impl Stack {
+    const CLOSING_CHARS: [char; 4] = [')', ']', '}', '>'];
+    const OPENING_CHARS: [char; 4] = ['(', '[', '{', '<'];
+
+    fn new(data: Vec<char>) -> Self {
+        Self { data }
+    }
+
+    pub fn complete(input: &str) -> Option<(String, usize)> {
+        Self::complete_into(input, &mut stdout(), Theme::current())
+    }
+
+    pub fn complete_into(
+        input: &str,
+        out: &mut impl Write,
+        theme: &Theme,
+    ) -> Option<(String, usize)> {
+        let mut stack = Stack::new(Vec::new());
+        let mut found_corruption = false;
+        for character in input.chars() {
+            if !stack.process_char(character, out, theme) {
+                found_corruption = true;
+            }
+        }
+        // Stil would like to visualize the output:
+        let result = stack
+            .data
+            .into_iter()
+            .enumerate()
+            .rev()
+            .map(|(index, opener)| (index, Self::opener_to_closer(opener)))
+            .filter(|(_, closer)| closer.is_some())
+            .map(|(index, closer)| (index, closer.unwrap()))
+            .map(|(index, closer)| {
+                writeln!(
+                    out,
+                    "{}{}",
+                    " ".repeat(index),
+                    theme.paint(&closer.to_string(), theme.palette.ok)
+                )
+                .unwrap();
+                closer
+            })
+            .fold((String::new(), 0), |(string, score), character| {
+                (
+                    format!("{}{}", string, character),
+                    score * 5 + Self::char_to_completion_score(character),
+                )
+            });
+        if found_corruption {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    #[cfg(test)]
+    fn is_corrupt(input: &str) -> bool {
+        let mut stack = Stack::new(Vec::new());
+        let theme = Theme::plain();
+        !input
+            .chars()
+            .all(|character| stack.process_char(character, &mut std::io::sink(), &theme))
+    }
+
+    pub fn calculate_corruption_score(input: &str) -> usize {
+        Self::corruption_score_into(input, &mut stdout(), Theme::current())
+    }
+
+    pub fn corruption_score_into(input: &str, out: &mut impl Write, theme: &Theme) -> usize {
+        let mut stack = Stack::new(Vec::new());
+        let mut counts = HashMap::new();
+        for character in input.chars() {
+            if !stack.process_char(character, out, theme) {
+                *counts.entry(character).or_insert(0) += 1;
+            }
+        }
+        counts
+            .into_iter()
+            .map(|(key, value)| Self::char_to_corruption_score(key) * value)
+            .sum()
+    }
+
+    fn process_char(&mut self, c: char, out: &mut impl Write, theme: &Theme) -> bool {
+        if Self::OPENING_CHARS.contains(&c) {
+            self.data.push(c);
+            writeln!(out, "{}{}", " ".repeat(self.data.len() - 1), c).unwrap();
+            true
+        } else if Self::CLOSING_CHARS.contains(&c) {
+            if let Some(opener) = self.data.pop() {
+                if let Some(closer) = Self::opener_to_closer(opener) {
+                    if closer == c {
+                        writeln!(out, "{}{}", " ".repeat(self.data.len()), c).unwrap();
+                        true
+                    } else {
+                        writeln!(
+                            out,
+                            "{}{} {} {}",
+                            " ".repeat(self.data.len()),
+                            theme.paint(&c.to_string(), theme.palette.error),
+                            theme.glyphs("≠"),
+                            theme.paint(&closer.to_string(), theme.palette.ok)
+                        )
+                        .unwrap();
+                        false
+                    }
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        } else {
+            false
+        }
+    }
+
+    fn opener_to_closer(c: char) -> Option<char> {
+        match c {
+            '(' => Some(')'),
+            '[' => Some(']'),
+            '{' => Some('}'),
+            '<' => Some('>'),
+            _ => None,
+        }
+    }
+
+    fn char_to_corruption_score(c: char) -> usize {
+        match c {
+            ')' => 3,
+            ']' => 57,
+            '}' => 1197,
+            '>' => 25137,
+            _ => 0,
+        }
+    }
+
+    fn char_to_completion_score(c: char) -> usize {
+        match c {
+            ')' => 1,
+            ']' => 2,
+            '}' => 3,
+            '>' => 4,
+            _ => 0,
+        }
+    }
+}
+
+pub fn completion_score(input: &str) -> Option<usize> {
+    completion_score_into(input, &mut stdout(), Theme::current())
+}
+
+pub fn completion_score_into(input: &str, out: &mut impl Write, theme: &Theme) -> Option<usize> {
+    let mut score: Vec<usize> = input
+        .lines()
+        .flat_map(|line| Stack::complete_into(line, out, theme))
+        .map(|(_, score)| score)
+        .collect();
+    score.sort_unstable();
+    score.get(score.len() / 2).copied()
+}
+
+pub fn read_chunks(input: &str) -> Result<Vec<&str>, String> {
+    input
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            match line
+                .chars()
+                .find(|c| !Stack::OPENING_CHARS.contains(c) && !Stack::CLOSING_CHARS.contains(c))
+            {
+                Some(c) => Err(format!("{}: {} is not a bracket", line, c)),
+                None => Ok(line),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::syntax_scoring::{completion_score_into, read_chunks, Stack};
+    use crate::theme::Theme;
+
+    const TEST_SET: &str = "[({(<(())[]>[[{[]{<()<>>
+[(()[<>])]({[<{<<[]>>(
+{([(<{}[<>[]}>{[]{[(<()>
+(((({<>}<{<{<>}{[]{[]{}
+[[<[([]))<([[{}[[()]]]
+[{[{({}]{}}([{[{{{}}([]
+{<[[]]>}<{[{[{[]{()[[[]
+[<(<(<(<{}))><([]([]()
+<{([([[(<>()){}]>(<<{{
+<{([{{}}[<[[[<>{}]]]>[]]";
+
+    #[test]
+    fn test_low_points() {
+        let score: usize = TEST_SET
+            .lines()
+            .map(Stack::calculate_corruption_score)
+            .inspect(|score| println!("Score: {}", score))
+            .sum();
+        assert_eq!(26397, score);
+    }
+
+    #[test]
+    fn test_completion() {
+        let mut score: Vec<usize> = TEST_SET
+            .lines()
+            .filter(|line| !Stack::is_corrupt(line))
+            .map(|line| (line, Stack::complete(line)))
+            .filter(|(_, option)| option.is_some())
+            .map(|(line, option)| (line, option.unwrap()))
+            .inspect(|(line, (completion, score))| {
+                println!("{} -> {} (score: {})", line, completion, score)
+            })
+            .map(|(_, (_, score))| score)
+            .collect();
+        score.sort();
+        assert_eq!(288957, score[score.len() / 2])
+    }
+
+    #[test]
+    fn test_bad_chunks() {
+        assert_eq!(Ok(vec!["[<>]", "(("]), read_chunks("[<>]\n\n((\n"));
+        assert_eq!(
+            Err("[<x>]: x is not a bracket".to_string()),
+            read_chunks("()\n[<x>]")
+        );
+        assert_eq!(
+            None,
+            completion_score_into("[<>)", &mut std::io::sink(), &Theme::plain())
+        );
+    }
+}
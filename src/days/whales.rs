@@ -0,0 +1,122 @@
+use crate::params::Parameter;
+use std::collections::HashMap;
+
+pub static PARAMETERS: [Parameter; 1] = [Parameter::choice(
+    "fuel",
+    "fuel a crab burns to move, per step or increasing with every step",
+    &["trivial", "triangular"],
+    ["trivial", "triangular"],
+)];
+
+pub fn mean(positions: &[usize]) -> usize {
+    positions.iter().sum::<usize>() / positions.len()
+}
+
+pub fn median(positions: &[usize]) -> usize {
+    let mut positions = positions.to_vec();
+    positions.sort_unstable();
+    positions[positions.len() / 2]
+}
+
+pub fn mode(positions: &[usize]) -> usize {
+    let mut counts = HashMap::new();
+
+    positions
+        .iter()
+        .copied()
+        .max_by_key(|&n| {
+            let count = counts.entry(n).or_insert(0);
+            *count += 1;
+            *count
+        })
+        .unwrap()
+}
+
+pub fn trivial_fuel_function(moved_positions: usize) -> usize {
+    moved_positions
+}
+
+pub fn triangular_fuel_function(moved_positions: usize) -> usize {
+    ((moved_positions as f32 / 2.0) * (moved_positions as f32 + 1.0)) as usize
+}
+
+pub fn fuel_function(name: &str) -> fn(usize) -> usize {
+    match name {
+        "triangular" => triangular_fuel_function,
+        _ => trivial_fuel_function,
+    }
+}
+
+pub fn align_crabs(
+    positions: &[usize],
+    fuel_function: fn(usize) -> usize,
+    destination: usize,
+) -> usize {
+    positions
+        .iter()
+        .map(|position| position.abs_diff(destination))
+        .map(fuel_function)
+        .sum()
+}
+
+pub fn optimize_alignment(
+    positions: &[usize],
+    fuel_function: fn(usize) -> usize,
+) -> (usize, usize) {
+    let lowest = *positions.iter().min().unwrap();
+    let highest = *positions.iter().max().unwrap();
+    (lowest..=highest)
+        .map(|statistic| (statistic, align_crabs(positions, fuel_function, statistic)))
+        .min_by_key(|(_, fuel_consumption)| *fuel_consumption)
+        .unwrap()
+}
+
+pub fn crab_positions(input: &str) -> Result<Vec<usize>, String> {
+    input
+        .trim()
+        .split(',')
+        .map(|numstr| {
+            numstr
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Not a position: {}", numstr))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::days::whales::{
+        crab_positions, optimize_alignment, triangular_fuel_function, trivial_fuel_function,
+    };
+
+    const TEST_SET: &str = "16,1,2,0,4,2,7,1,2,14";
+
+    #[test]
+    fn test_crab_alignment() {
+        let crab_positions = crab_positions(TEST_SET).unwrap();
+        let (position, fuel_consumption) =
+            optimize_alignment(&crab_positions, trivial_fuel_function);
+        assert_eq!(2, position);
+        assert_eq!(37, fuel_consumption);
+    }
+
+    #[test]
+    fn test_alternative_crab_alignment() {
+        let crab_positions = crab_positions(TEST_SET).unwrap();
+        let (position, fuel_consumption) =
+            optimize_alignment(&crab_positions, triangular_fuel_function);
+        assert_eq!(5, position);
+        assert_eq!(168, fuel_consumption);
+    }
+
+    #[test]
+    fn test_bad_positions() {
+        assert_eq!(Ok(vec![16, 1]), crab_positions("16,1\n"));
+        assert_eq!(
+            Err("Not a position: x".to_string()),
+            crab_positions("16,x,2")
+        );
+        assert!(crab_positions("").is_err());
+    }
+}
@@ -0,0 +1,124 @@
+use crate::params::Overrides;
+use crate::solver::{solve, SolveError};
+use std::os::raw::{c_char, c_int};
+
+/// Status codes returned by `aoc_solve`.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AocStatus {
+    Ok = 0,
+    UnknownPuzzle = 1,
+    InvalidInput = 2,
+    InvalidUtf8 = 3,
+    BufferTooSmall = 4,
+    NullPointer = 5,
+    InvalidParameter = 6,
+}
+
+/// Caller owned buffer receiving the NUL terminated answer, or the error message on failure.
+/// `length` is always set to the length the full text needs (without the NUL), so a call that
+/// returned `AOC_STATUS_BUFFER_TOO_SMALL` can be repeated with a bigger buffer.
+#[repr(C)]
+pub struct AocBuffer {
+    pub data: *mut c_char,
+    pub capacity: usize,
+    pub length: usize,
+}
+
+impl From<&SolveError> for AocStatus {
+    fn from(error: &SolveError) -> Self {
+        match error {
+            SolveError::UnknownPuzzle { .. } => AocStatus::UnknownPuzzle,
+            SolveError::InvalidParameter(_) => AocStatus::InvalidParameter,
+            SolveError::InvalidInput(_) => AocStatus::InvalidInput,
+        }
+    }
+}
+
+/// Solves one part of a puzzle for the input of `len` bytes at `input_ptr`.
+///
+/// # Safety
+///
+/// `input_ptr` must point to `len` readable bytes and `out_buf` to an `AocBuffer` whose `data`
+/// points to `capacity` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input_ptr: *const u8,
+    len: usize,
+    out_buf: *mut AocBuffer,
+) -> AocStatus {
+    if out_buf.is_null() || (input_ptr.is_null() && len > 0) {
+        return AocStatus::NullPointer;
+    }
+    let out = &mut *out_buf;
+    if out.data.is_null() && out.capacity > 0 {
+        return AocStatus::NullPointer;
+    }
+
+    let input = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(input_ptr, len)
+    };
+    let (status, text) = match std::str::from_utf8(input) {
+        Err(error) => (AocStatus::InvalidUtf8, error.to_string()),
+        Ok(input) => match solve(year, day, part, input, &Overrides::default(), false) {
+            Ok(solution) => (AocStatus::Ok, solution.answer),
+            Err(error) => (AocStatus::from(&error), error.to_string()),
+        },
+    };
+
+    out.length = text.len();
+    if text.len() >= out.capacity {
+        if out.capacity > 0 {
+            *out.data = 0;
+        }
+        return if status == AocStatus::Ok {
+            AocStatus::BufferTooSmall
+        } else {
+            status
+        };
+    }
+    std::ptr::copy_nonoverlapping(text.as_ptr(), out.data as *mut u8, text.len());
+    *out.data.add(text.len()) = 0;
+
+    status
+}
+
+/// Returns a static, NUL terminated description of a status code.
+#[no_mangle]
+pub extern "C" fn aoc_status_message(status: c_int) -> *const c_char {
+    const MESSAGES: [&[u8]; 7] = [
+        b"ok\0",
+        b"no solver for this puzzle\0",
+        b"invalid puzzle input\0",
+        b"input is not valid UTF-8\0",
+        b"output buffer too small\0",
+        b"null pointer argument\0",
+        b"invalid solver parameter\0",
+    ];
+    let message = usize::try_from(status)
+        .ok()
+        .and_then(|status| MESSAGES.get(status))
+        .copied()
+        .unwrap_or(b"unknown status\0");
+    message.as_ptr() as *const c_char
+}
+
+#[cfg(test)]
+mod tests {
+    /// The build generates the header into its output directory, the one shipped is kept in sync
+    /// by hand.
+    #[test]
+    fn test_header() {
+        assert!(
+            include_str!("../include/advent_of_code.h")
+                == include_str!(concat!(env!("OUT_DIR"), "/advent_of_code.h")),
+            "include/advent_of_code.h is out of date, copy it from {}/advent_of_code.h",
+            env!("OUT_DIR")
+        );
+    }
+}
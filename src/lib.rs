@@ -1,5 +1,13 @@
+pub mod bignum;
+pub mod canvas;
+pub mod days;
+pub mod ffi;
+pub mod params;
+pub mod solver;
+pub mod theme;
+
 #[macro_export]
-macro_rules! read_input {
+macro_rules! input_path {
     () => {{
         let filename = file!();
         let filename = filename
@@ -7,10 +15,25 @@ macro_rules! read_input {
             .unwrap()
             .strip_prefix("src/bin/")
             .unwrap();
-        let path = std::env::current_dir()
+        std::env::current_dir()
             .unwrap()
-            .join(format!("res/{}.txt", filename));
-        std::fs::read_to_string(path).expect("File does not exist!")
+            .join(format!("res/{}.txt", filename))
+    }};
+}
+
+#[macro_export]
+macro_rules! read_input {
+    () => {{
+        std::fs::read_to_string(advent_of_code::input_path!()).expect("File does not exist!")
+    }};
+}
+
+#[macro_export]
+macro_rules! open_input {
+    () => {{
+        std::io::BufReader::new(
+            std::fs::File::open(advent_of_code::input_path!()).expect("File does not exist!"),
+        )
     }};
 }
 
@@ -27,9 +50,17 @@ macro_rules! read_input_lines {
 #[macro_export]
 macro_rules! read_input_ints {
     () => {{
+        advent_of_code::read_input_ints!(u32)
+    }};
+    ($type:ty) => {{
         advent_of_code::read_input_lines!()
             .iter()
-            .map(|string| string.parse::<u32>().expect("Not all lines are integers"))
-            .collect()
+            .map(|string| {
+                string
+                    .trim()
+                    .parse::<$type>()
+                    .expect("Not all lines are numbers")
+            })
+            .collect::<Vec<$type>>()
     }};
 }
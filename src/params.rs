@@ -0,0 +1,395 @@
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Kind {
+    Integer { min: u64, max: u64 },
+    Choice(&'static [&'static str]),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Integer(u64),
+    /// One of the options, or a value with the prefix of the parameter.
+    Choice(Cow<'static, str>),
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Integer(value) => write!(f, "{}", value),
+            Value::Choice(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Checks the argument of a prefixed choice, returning why it is invalid.
+pub type Check = fn(&str) -> Result<(), String>;
+
+/// A tunable value of a puzzle, with a default for each part.
+#[derive(Clone, Debug)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub kind: Kind,
+    pub defaults: [Value; 2],
+    /// Lets a choice also take `<prefix>:<argument>`, for the arguments the check accepts.
+    pub prefixed: Option<(&'static str, Check)>,
+}
+
+impl Parameter {
+    pub const fn integer(
+        name: &'static str,
+        description: &'static str,
+        min: u64,
+        max: u64,
+        defaults: [u64; 2],
+    ) -> Self {
+        Self {
+            name,
+            description,
+            kind: Kind::Integer { min, max },
+            defaults: [Value::Integer(defaults[0]), Value::Integer(defaults[1])],
+            prefixed: None,
+        }
+    }
+
+    pub const fn choice(
+        name: &'static str,
+        description: &'static str,
+        options: &'static [&'static str],
+        defaults: [&'static str; 2],
+    ) -> Self {
+        Self {
+            name,
+            description,
+            kind: Kind::Choice(options),
+            defaults: [
+                Value::Choice(Cow::Borrowed(defaults[0])),
+                Value::Choice(Cow::Borrowed(defaults[1])),
+            ],
+            prefixed: None,
+        }
+    }
+
+    /// Accepts `<prefix>:<argument>` besides the options of a choice, when `check` accepts the
+    /// argument.
+    pub const fn or_prefixed(mut self, prefix: &'static str, check: Check) -> Self {
+        self.prefixed = Some((prefix, check));
+        self
+    }
+
+    pub fn parse(&self, value: &str) -> Result<Value, String> {
+        match self.kind {
+            Kind::Integer { min, max } => match value.trim().parse::<u64>() {
+                Ok(value) if (min..=max).contains(&value) => Ok(Value::Integer(value)),
+                _ => Err(format!(
+                    "{} should be an integer from {} to {}, not {}",
+                    self.name, min, max, value
+                )),
+            },
+            Kind::Choice(options) => {
+                if let Some(option) = options.iter().find(|option| **option == value.trim()) {
+                    return Ok(Value::Choice(Cow::Borrowed(option)));
+                }
+                match (self.prefixed, value.trim().split_once(':')) {
+                    (Some((prefix, check)), Some((given, argument))) if given == prefix => {
+                        check(argument)
+                            .map_err(|reason| format!("{} {}: {}", self.name, value, reason))?;
+                        Ok(Value::Choice(Cow::Owned(value.trim().to_string())))
+                    }
+                    (Some((prefix, _)), _) => Err(format!(
+                        "{} should be one of {:?} or {}:<...>, not {}",
+                        self.name, options, prefix, value
+                    )),
+                    (None, _) => Err(format!(
+                        "{} should be one of {:?}, not {}",
+                        self.name, options, value
+                    )),
+                }
+            }
+        }
+    }
+
+    fn default(&self, part: u8) -> Value {
+        self.defaults[if part == 2 { 1 } else { 0 }].clone()
+    }
+}
+
+/// Parameter values as given by the user, not yet checked against what a day declares.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Overrides(BTreeMap<String, String>);
+
+impl Overrides {
+    pub const CONFIG_FILE: &'static str = "aoc.toml";
+
+    pub fn insert(&mut self, name: &str, value: &str) {
+        self.0
+            .insert(name.trim().to_string(), value.trim().to_string());
+    }
+
+    pub fn remove(&mut self, name: &str) -> bool {
+        self.0.remove(name).is_some()
+    }
+
+    /// Parses a `name=value` pair, as passed to `--param`.
+    pub fn assign(&mut self, assignment: &str) -> Result<(), String> {
+        let (name, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected name=value, got {}", assignment))?;
+        self.insert(name, value);
+        Ok(())
+    }
+
+    /// Reads the `[day<N>]` table of a TOML config, e.g. `[day6]` followed by `days = 1000`.
+    pub fn from_config(config: &str, day: u8) -> Result<Self, String> {
+        let config: toml::Table = toml::from_str(config).map_err(|error| error.to_string())?;
+        let mut overrides = Self::default();
+        if let Some(table) = config.get(&format!("day{}", day)) {
+            let table = table
+                .as_table()
+                .ok_or_else(|| format!("day{} should be a table", day))?;
+            for (name, value) in table {
+                match value {
+                    toml::Value::String(value) => overrides.insert(name, value),
+                    toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) => {
+                        overrides.insert(name, &value.to_string())
+                    }
+                    _ => return Err(format!("day{}.{} should be a plain value", day, name)),
+                }
+            }
+        }
+        Ok(overrides)
+    }
+
+    /// Collects `--param name=value` and `--config <file>` from the arguments, returning the
+    /// arguments it did not recognize. Without `--config`, `aoc.toml` is read when it exists.
+    /// Parameters on the command line win over the ones in the config.
+    pub fn from_args(args: &[String], day: u8) -> Result<(Self, Vec<String>), String> {
+        let mut config = None;
+        let mut overrides = Self::default();
+        let mut rest = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--param" => overrides.assign(args.next().ok_or("--param needs name=value")?)?,
+                "--config" => config = Some(args.next().ok_or("--config needs a file")?.clone()),
+                _ => rest.push(arg.clone()),
+            }
+        }
+
+        let config = match config {
+            Some(path) => Some(
+                std::fs::read_to_string(&path).map_err(|error| format!("{}: {}", path, error))?,
+            ),
+            None if Path::new(Self::CONFIG_FILE).exists() => {
+                std::fs::read_to_string(Self::CONFIG_FILE).ok()
+            }
+            None => None,
+        };
+        let mut merged = match config {
+            Some(config) => Self::from_config(&config, day)?,
+            None => Self::default(),
+        };
+        merged.0.extend(overrides.0);
+
+        Ok((merged, rest))
+    }
+}
+
+/// The effective parameters of one part of a day.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Parameters(Vec<(&'static str, Value)>);
+
+impl Parameters {
+    pub fn resolve(
+        declared: &[Parameter],
+        part: u8,
+        overrides: &Overrides,
+    ) -> Result<Self, String> {
+        if let Some(unknown) = overrides
+            .0
+            .keys()
+            .find(|name| !declared.iter().any(|parameter| parameter.name == *name))
+        {
+            return Err(format!("Unknown parameter: {}", unknown));
+        }
+        declared
+            .iter()
+            .map(|parameter| {
+                let value = match overrides.0.get(parameter.name) {
+                    Some(value) => parameter.parse(value)?,
+                    None => parameter.default(part),
+                };
+                Ok((parameter.name, value))
+            })
+            .collect::<Result<Vec<_>, String>>()
+            .map(Parameters)
+    }
+
+    /// Resolves both parts from `--param` and `--config` in the arguments, returning the
+    /// arguments it did not recognize.
+    pub fn from_args(
+        declared: &[Parameter],
+        day: u8,
+        args: &[String],
+    ) -> Result<([Self; 2], Vec<String>), String> {
+        let (overrides, rest) = Overrides::from_args(args, day)?;
+        let parts = [
+            Self::resolve(declared, 1, &overrides)?,
+            Self::resolve(declared, 2, &overrides)?,
+        ];
+        Ok((parts, rest))
+    }
+
+    /// Resolves both parts from the command line of a day's binary, exiting when they are
+    /// invalid.
+    pub fn from_command_line(declared: &[Parameter], day: u8) -> [Self; 2] {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        let resolved =
+            Self::from_args(declared, day, &args).and_then(|(parts, rest)| match rest.first() {
+                Some(arg) => Err(format!("Unexpected argument: {}", arg)),
+                None => Ok(parts),
+            });
+        resolved.unwrap_or_else(|message| {
+            eprintln!("{}", message);
+            std::process::exit(1)
+        })
+    }
+
+    pub fn defaults(declared: &[Parameter], part: u8) -> Self {
+        Self::resolve(declared, part, &Overrides::default()).unwrap()
+    }
+
+    fn get(&self, name: &str) -> &Value {
+        self.0
+            .iter()
+            .find(|(declared, _)| *declared == name)
+            .map(|(_, value)| value)
+            .unwrap_or_else(|| panic!("Parameter {} was never declared", name))
+    }
+
+    pub fn integer(&self, name: &str) -> u64 {
+        match self.get(name) {
+            Value::Integer(value) => *value,
+            value => panic!("Parameter {} is not an integer: {}", name, value),
+        }
+    }
+
+    pub fn choice(&self, name: &str) -> &str {
+        match self.get(name) {
+            Value::Choice(value) => value,
+            value => panic!("Parameter {} is not a choice: {}", name, value),
+        }
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(&'static str, Value)> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for Parameters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let pairs: Vec<String> = self
+            .0
+            .iter()
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect();
+        write!(f, "{}", pairs.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::params::{Overrides, Parameter, Parameters, Value};
+
+    const DECLARED: [Parameter; 2] = [
+        Parameter::integer("days", "how long", 0, 10_000, [80, 256]),
+        Parameter::choice(
+            "fuel",
+            "how",
+            &["trivial", "triangular"],
+            ["trivial", "triangular"],
+        ),
+    ];
+
+    #[test]
+    fn test_defaults() {
+        let parameters = Parameters::defaults(&DECLARED, 2);
+        assert_eq!(256, parameters.integer("days"));
+        assert_eq!("triangular", parameters.choice("fuel"));
+        assert_eq!("days=256, fuel=triangular", parameters.to_string());
+    }
+
+    #[test]
+    fn test_overrides() {
+        let mut overrides =
+            Overrides::from_config("[day6]\ndays = 18\nfuel = \"triangular\"", 6).unwrap();
+        overrides.assign("days=1000").unwrap();
+        let parameters = Parameters::resolve(&DECLARED, 1, &overrides).unwrap();
+        assert_eq!(1000, parameters.integer("days"));
+        assert_eq!("triangular", parameters.choice("fuel"));
+
+        assert_eq!(
+            Overrides::default(),
+            Overrides::from_config("[day6]\ndays = 18", 7).unwrap()
+        );
+        assert!(overrides.assign("days").is_err());
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut overrides = Overrides::default();
+        overrides.insert("days", "-1");
+        assert!(Parameters::resolve(&DECLARED, 1, &overrides).is_err());
+        overrides.insert("days", "10001");
+        assert_eq!(
+            Err("days should be an integer from 0 to 10000, not 10001".to_string()),
+            Parameters::resolve(&DECLARED, 1, &overrides).map(|_| ())
+        );
+
+        let mut overrides = Overrides::default();
+        overrides.insert("fuel", "diesel");
+        assert!(Parameters::resolve(&DECLARED, 1, &overrides).is_err());
+
+        let mut overrides = Overrides::default();
+        overrides.insert("weeks", "3");
+        assert!(Parameters::resolve(&DECLARED, 1, &overrides).is_err());
+        assert_eq!(Ok(Value::Integer(3)), DECLARED[0].parse(" 3 "));
+    }
+
+    #[test]
+    fn test_prefixed() {
+        let rule = Parameter::choice("rule", "which", &["lines"], ["lines", "lines"]).or_prefixed(
+            "pattern",
+            |mask| match mask.is_empty() {
+                true => Err("needs a mask".to_string()),
+                false => Ok(()),
+            },
+        );
+        assert_eq!(
+            Ok(Value::Choice("pattern:x.x".into())),
+            rule.parse(" pattern:x.x")
+        );
+        assert_eq!(
+            Err("rule pattern:: needs a mask".to_string()),
+            rule.parse("pattern:")
+        );
+        assert_eq!(
+            Err("rule should be one of [\"lines\"] or pattern:<...>, not stripes:x".to_string()),
+            rule.parse("stripes:x")
+        );
+
+        let mut overrides = Overrides::default();
+        overrides.insert("rule", "pattern:x.x");
+        let parameters = Parameters::resolve(&[rule], 1, &overrides).unwrap();
+        assert_eq!("pattern:x.x", parameters.choice("rule"));
+        assert_eq!("rule=pattern:x.x", parameters.to_string());
+    }
+}
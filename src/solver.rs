@@ -0,0 +1,416 @@
+use crate::days::bingo::{self, bingo_loser, bingo_winner, prepare, ranking, ranking_table};
+use crate::days::diagnostic::{
+    self, criteria_filter, diagnose, DiagnosticError, TiePolicy, CO2_SCRUB, O2_GEN,
+};
+use crate::days::diagnostic_stats;
+use crate::days::dive::{self, trace, BreachPolicy, Direction};
+use crate::days::dive_chart;
+use crate::days::hydrothermal_venture::{
+    count_intersections, generate_field, input_to_lines, render_field,
+};
+use crate::days::lanternfish::{self, get_init_state, Shoaling};
+use crate::days::seven_segment::{count_unique_digits, read_input_digits, Display};
+use crate::days::smoke_basin::{
+    self, find_low_points, largest_basins, read_input_to_map, HeightMap,
+};
+use crate::days::sonar_chart::{braille_chart, HEIGHT, WIDTH};
+use crate::days::sonar_sweep::{self, filtered_sonar_sweep, read_depths, Depth};
+use crate::days::syntax_scoring::{completion_score_into, read_chunks, Stack};
+use crate::days::whales::{self, crab_positions, fuel_function, optimize_alignment};
+use crate::params::{Overrides, Parameter, Parameters};
+use crate::theme::Theme;
+use std::any::Any;
+use std::fmt::Formatter;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+pub const YEAR: u16 = 2021;
+
+#[derive(Debug, PartialEq)]
+pub enum SolveError {
+    UnknownPuzzle { year: u16, day: u8, part: u8 },
+    InvalidParameter(String),
+    InvalidInput(String),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownPuzzle { year, day, part } => {
+                write!(f, "No solver for {} day {} part {}", year, day, part)
+            }
+            SolveError::InvalidParameter(reason) => write!(f, "Invalid parameter: {}", reason),
+            SolveError::InvalidInput(reason) => write!(f, "Invalid input: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+#[derive(Debug)]
+pub struct Solution {
+    pub answer: String,
+    pub elapsed: Duration,
+    pub parameters: Parameters,
+    pub visualization: Option<String>,
+}
+
+/// The parameters a day declares, shared by both its parts.
+pub fn parameters(day: u8) -> &'static [Parameter] {
+    match day {
+        1 => &sonar_sweep::PARAMETERS,
+        2 => &dive::PARAMETERS,
+        3 => &diagnostic::PARAMETERS,
+        4 => &bingo::PARAMETERS,
+        6 => &lanternfish::PARAMETERS,
+        7 => &whales::PARAMETERS,
+        9 => &smoke_basin::PARAMETERS,
+        _ => &[],
+    }
+}
+
+/// Runs a single part of a puzzle on the given input. Visualizations are rendered without colors,
+/// so they can be shown anywhere.
+pub fn solve(
+    year: u16,
+    day: u8,
+    part: u8,
+    input: &str,
+    overrides: &Overrides,
+    visualize: bool,
+) -> Result<Solution, SolveError> {
+    if year != YEAR {
+        return Err(SolveError::UnknownPuzzle { year, day, part });
+    }
+    let parameters = Parameters::resolve(parameters(day), part, overrides)
+        .map_err(SolveError::InvalidParameter)?;
+    let theme = Theme::plain();
+    let theme = if visualize { Some(&theme) } else { None };
+
+    let start = Instant::now();
+    // The parsers still panic on malformed input, which should not take the caller down with it
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        run(day, part, input, &parameters, theme)
+    }))
+    .map_err(|payload| SolveError::InvalidInput(panic_reason(payload.as_ref())))??;
+    let elapsed = start.elapsed();
+
+    match result {
+        Some((answer, visualization)) => Ok(Solution {
+            answer,
+            elapsed,
+            parameters,
+            visualization,
+        }),
+        None => Err(SolveError::UnknownPuzzle { year, day, part }),
+    }
+}
+
+/// The message a caught panic was raised with.
+pub fn panic_reason(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|reason| reason.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Solver panicked".to_string())
+}
+
+type Answer = Option<(String, Option<String>)>;
+
+fn run(
+    day: u8,
+    part: u8,
+    input: &str,
+    parameters: &Parameters,
+    theme: Option<&Theme>,
+) -> Result<Answer, SolveError> {
+    let answer = |answer: usize| Ok(Some((answer.to_string(), None)));
+
+    match (day, part) {
+        (1, 1 | 2) => {
+            let window = parameters.integer("window") as usize;
+            // Whole depths are compared exactly, fractional ones (or NaN for a gap) as f64
+            match depths::<i64>(input) {
+                Ok(depths) => sonar(depths, window, theme),
+                Err(_) => sonar(depths::<f64>(input)?, window, theme),
+            }
+        }
+
+        (2, 1 | 2) => {
+            let directions = lines(input)
+                .iter()
+                .map(|line| line.parse::<Direction>())
+                .collect::<Result<Vec<Direction>, _>>()
+                .map_err(|reason| SolveError::InvalidInput(reason.to_string()))?;
+            let policy = parameters
+                .choice("surface")
+                .parse::<BreachPolicy>()
+                .unwrap();
+            let model = dive::model(parameters.choice("model")).unwrap();
+            let trajectory = trace(&directions, model, policy)
+                .map_err(|error| SolveError::InvalidInput(error.to_string()))?;
+            let product = trajectory.end().product().ok_or_else(|| {
+                SolveError::InvalidInput("The product is out of range".to_string())
+            })?;
+            let visualization = theme.map(|theme| {
+                dive_chart::plot(&trajectory, dive_chart::WIDTH, dive_chart::HEIGHT, theme)
+            });
+            Ok(Some((product.to_string(), visualization)))
+        }
+
+        (3, 1 | 2) => {
+            let policy = parameters.choice("ties").parse::<TiePolicy>().unwrap();
+            let invalid = |error: DiagnosticError| SolveError::InvalidInput(error.to_string());
+            let answer = if part == 1 {
+                diagnose(&lines(input), policy).map_err(invalid)?
+            } else {
+                criteria_filter(lines(input), O2_GEN, policy)
+                    .map_err(invalid)?
+                    .value
+                    * criteria_filter(lines(input), CO2_SCRUB, policy)
+                        .map_err(invalid)?
+                        .value
+            };
+            let visualization = theme.and_then(|theme| {
+                let statistics = diagnostic_stats::statistics(&lines(input)).ok()?;
+                Some(diagnostic_stats::bias_chart(
+                    &statistics,
+                    diagnostic_stats::WIDTH,
+                    theme,
+                ))
+            });
+            Ok(Some((answer.to_string(), visualization)))
+        }
+
+        (4, 1 | 2) => {
+            let (order, boards) =
+                prepare(input).map_err(|error| SolveError::InvalidInput(error.to_string()))?;
+            let rule =
+                bingo::rule(parameters.choice("rule")).map_err(SolveError::InvalidParameter)?;
+            let rule = rule.as_ref();
+            let score = if part == 1 {
+                bingo_winner(&order, &boards, rule)
+            } else {
+                bingo_loser(&order, &boards, rule)
+            }
+            .ok_or_else(|| SolveError::InvalidInput("No board ever wins".to_string()))?;
+            let visualization = theme.map(|_| ranking_table(&ranking(&order, &boards, rule)));
+            Ok(Some((score.to_string(), visualization)))
+        }
+
+        (5, 1 | 2) => {
+            let lines = input_to_lines(lines(input))
+                .map_err(SolveError::InvalidInput)?
+                .into_iter()
+                .filter(|line| part == 2 || line.is_horizontal() || line.is_vertical())
+                .collect();
+            let field = generate_field(lines);
+            let visualization = theme.map(|theme| render_field(&field, theme));
+            Ok(Some((
+                count_intersections(field).to_string(),
+                visualization,
+            )))
+        }
+
+        (6, 1 | 2) => {
+            let ages = get_init_state(input.to_string()).map_err(SolveError::InvalidInput)?;
+            Ok(Some((
+                Shoaling::from_ages(ages)
+                    .progress_n_days(parameters.integer("days"))
+                    .fish_count()
+                    .to_string(),
+                None,
+            )))
+        }
+
+        (7, 1 | 2) => {
+            let positions = crab_positions(input).map_err(SolveError::InvalidInput)?;
+            answer(optimize_alignment(&positions, fuel_function(parameters.choice("fuel"))).1)
+        }
+
+        (8, 1 | 2) => {
+            let entries = read_input_digits(input).map_err(SolveError::InvalidInput)?;
+            let result: usize = if part == 1 {
+                entries
+                    .into_iter()
+                    .map(|(_, output)| count_unique_digits(output))
+                    .sum()
+            } else {
+                entries
+                    .into_iter()
+                    .map(|(notes, output)| Display::from_notes(notes).decode_number(output))
+                    .sum()
+            };
+            let visualization =
+                theme.map(|theme| Display::display(result, theme, theme.palette.total));
+            Ok(Some((result.to_string(), visualization)))
+        }
+
+        (9, 1 | 2) => {
+            let rows = read_input_to_map(input).map_err(SolveError::InvalidInput)?;
+            let low_points = find_low_points(&rows);
+            let result = if part == 1 {
+                low_points.iter().map(|point| point.risk()).sum()
+            } else {
+                largest_basins(
+                    &rows,
+                    &low_points,
+                    parameters.integer("basins") as usize,
+                    parameters.integer("wall") as usize,
+                )
+            };
+            let visualization = theme.map(|theme| HeightMap { rows, low_points }.render(theme));
+            Ok(Some((result.to_string(), visualization)))
+        }
+
+        (10, 1 | 2) => {
+            let mut trace = Vec::new();
+            let plain = Theme::plain();
+            let trace_theme = theme.unwrap_or(&plain);
+            let chunks = read_chunks(input).map_err(SolveError::InvalidInput)?;
+            let result = if part == 1 {
+                chunks
+                    .iter()
+                    .map(|line| Stack::corruption_score_into(line, &mut trace, trace_theme))
+                    .sum()
+            } else {
+                completion_score_into(&chunks.join("\n"), &mut trace, trace_theme)
+                    .ok_or_else(|| SolveError::InvalidInput("No line is incomplete".to_string()))?
+            };
+            let visualization = theme.map(|_| String::from_utf8_lossy(&trace).into_owned());
+            Ok(Some((result.to_string(), visualization)))
+        }
+
+        _ => Ok(None),
+    }
+}
+
+fn lines(input: &str) -> Vec<&str> {
+    input
+        .split('\n')
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect()
+}
+
+fn depths<D: Depth>(input: &str) -> Result<Vec<D>, SolveError> {
+    read_depths(input.as_bytes())
+        .collect::<Result<_, _>>()
+        .map_err(|error| SolveError::InvalidInput(error.to_string()))
+}
+
+fn sonar<D: Depth>(
+    depths: Vec<D>,
+    window: usize,
+    theme: Option<&Theme>,
+) -> Result<Answer, SolveError> {
+    let visualization = theme.map(|theme| braille_chart(&depths, window, WIDTH, HEIGHT, theme));
+    Ok(Some((
+        filtered_sonar_sweep(depths, window).to_string(),
+        visualization,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::params::Overrides;
+    use crate::solver::{solve, SolveError};
+
+    #[test]
+    fn test_solve() {
+        let defaults = Overrides::default();
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+        assert_eq!(
+            "7",
+            solve(2021, 1, 1, input, &defaults, false).unwrap().answer
+        );
+        assert_eq!(
+            "5",
+            solve(2021, 1, 2, input, &defaults, false).unwrap().answer
+        );
+
+        let solution = solve(
+            2021,
+            9,
+            2,
+            "2199943210\n3987894921\n9856789892\n8767896789\n9899965678",
+            &defaults,
+            true,
+        )
+        .unwrap();
+        assert_eq!("1134", solution.answer);
+        assert!(solution.visualization.is_some());
+
+        // Above sea level and fractional depths, with a comment and a gap
+        let input = "# depth (m)\n-6\n-5\n3\n5\n-5\n2\n";
+        assert_eq!(
+            "4",
+            solve(2021, 1, 1, input, &defaults, true).unwrap().answer
+        );
+        let input = "-1.5\n-0.25\nNaN\n0.5\n0.5\n2\n";
+        assert_eq!(
+            "3",
+            solve(2021, 1, 1, input, &defaults, false).unwrap().answer
+        );
+
+        let mut overrides = Overrides::default();
+        overrides.insert("days", "18");
+        let solution = solve(2021, 6, 2, "3,4,3,1,2", &overrides, false).unwrap();
+        assert_eq!("26", solution.answer);
+        assert_eq!("days=18", solution.parameters.to_string());
+    }
+
+    #[test]
+    fn test_solve_errors() {
+        assert_eq!(
+            SolveError::UnknownPuzzle {
+                year: 2021,
+                day: 26,
+                part: 1
+            },
+            solve(2021, 26, 1, "", &Overrides::default(), false).unwrap_err()
+        );
+        assert!(matches!(
+            solve(2021, 1, 1, "12\nfish\n", &Overrides::default(), false),
+            Err(SolveError::InvalidInput(_))
+        ));
+        assert!(matches!(
+            solve(
+                2021,
+                4,
+                1,
+                "1,2,3\n\n1 2\n3 x",
+                &Overrides::default(),
+                false
+            ),
+            Err(SolveError::InvalidInput(_))
+        ));
+
+        let mut overrides = Overrides::default();
+        overrides.insert("surface", "clamp");
+        assert_eq!(
+            "2",
+            solve(2021, 2, 1, "forward 2\nup 3\ndown 1", &overrides, false)
+                .unwrap()
+                .answer
+        );
+        assert!(matches!(
+            solve(
+                2021,
+                2,
+                1,
+                "forward 2\nup 3\ndown 1",
+                &Overrides::default(),
+                false
+            ),
+            Err(SolveError::InvalidInput(_))
+        ));
+
+        let mut overrides = Overrides::default();
+        overrides.insert("wall", "8");
+        assert!(matches!(
+            solve(2021, 6, 1, "3,4,3,1,2", &overrides, false),
+            Err(SolveError::InvalidParameter(_))
+        ));
+    }
+}
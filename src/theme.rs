@@ -0,0 +1,460 @@
+use colored::{Color, ColoredString, Colorize};
+use serde::Deserialize;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+pub enum ColorDepth {
+    #[serde(rename = "none")]
+    None,
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "256")]
+    Ansi256,
+    #[serde(rename = "truecolor")]
+    TrueColor,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Capabilities {
+    pub color: ColorDepth,
+    pub unicode: bool,
+}
+
+impl Capabilities {
+    pub fn detect() -> Self {
+        Self::from_env(
+            |key| std::env::var(key).ok(),
+            std::io::stdout().is_terminal(),
+        )
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>, is_terminal: bool) -> Self {
+        let set = |key: &str| var(key).filter(|value| !value.is_empty());
+        let forced = set("CLICOLOR_FORCE").filter(|value| value != "0").is_some();
+        let term = set("TERM").unwrap_or_default();
+
+        let color = if set("NO_COLOR").is_some() || (!is_terminal && !forced) || term == "dumb" {
+            ColorDepth::None
+        } else if matches!(set("COLORTERM").as_deref(), Some("truecolor" | "24bit")) {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        // The first locale variable that is set wins, just like setlocale(3) does it
+        let unicode = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .find_map(|key| set(key))
+            .map(|locale| {
+                let locale = locale.to_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            })
+            .unwrap_or(false);
+
+        Self { color, unicode }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ThemeColor(pub Color);
+
+impl TryFrom<String> for ThemeColor {
+    type Error = String;
+
+    fn try_from(string: String) -> Result<Self, Self::Error> {
+        Color::from_str(&string)
+            .map(ThemeColor)
+            .map_err(|_| format!("Not a color name or #rrggbb value: {}", string))
+    }
+}
+
+impl ThemeColor {
+    /// The `#rrggbb` notation, for output that is not a terminal.
+    pub fn hex(&self) -> String {
+        let (r, g, b) = to_rgb(self.0);
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+const fn rgb(r: u8, g: u8, b: u8) -> ThemeColor {
+    ThemeColor(Color::TrueColor { r, g, b })
+}
+
+const fn named(color: Color) -> ThemeColor {
+    ThemeColor(color)
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Palette {
+    pub digit: ThemeColor,
+    pub total: ThemeColor,
+    pub segment_off: ThemeColor,
+    pub background: ThemeColor,
+    pub basin_floor: ThemeColor,
+    pub basin_wall: ThemeColor,
+    pub low_point: ThemeColor,
+    pub ok: ThemeColor,
+    pub error: ThemeColor,
+    pub rising: ThemeColor,
+    pub falling: ThemeColor,
+    pub smoothed: ThemeColor,
+    pub surface: ThemeColor,
+    pub ones: ThemeColor,
+    pub zeros: ThemeColor,
+    pub marked: ThemeColor,
+    pub winning: ThemeColor,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            digit: named(Color::Red),
+            total: named(Color::BrightGreen),
+            segment_off: named(Color::Black),
+            background: named(Color::Black),
+            basin_floor: rgb(0, 0, 0),
+            basin_wall: rgb(0, 0, 135),
+            low_point: rgb(0, 255, 0),
+            ok: named(Color::Green),
+            error: named(Color::Red),
+            rising: named(Color::Green),
+            falling: named(Color::Red),
+            smoothed: named(Color::BrightYellow),
+            surface: named(Color::Blue),
+            ones: named(Color::Cyan),
+            zeros: named(Color::Magenta),
+            marked: named(Color::Yellow),
+            winning: named(Color::BrightGreen),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeConfig {
+    color: Option<ColorDepth>,
+    unicode: Option<bool>,
+    palette: Palette,
+}
+
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub capabilities: Capabilities,
+    pub palette: Palette,
+}
+
+static CURRENT: OnceLock<Theme> = OnceLock::new();
+
+impl Theme {
+    const CONFIG_VARIABLE: &'static str = "AOC_THEME";
+    const CONFIG_FILE: &'static str = "theme.toml";
+
+    pub fn new(capabilities: Capabilities, palette: Palette) -> Self {
+        Self {
+            capabilities,
+            palette,
+        }
+    }
+
+    pub fn plain() -> Self {
+        Self::new(
+            Capabilities {
+                color: ColorDepth::None,
+                unicode: true,
+            },
+            Palette::default(),
+        )
+    }
+
+    /// The theme is detected once, from the environment and the optional config file pointed to
+    /// by `AOC_THEME` (or `theme.toml` in the working directory).
+    pub fn current() -> &'static Theme {
+        CURRENT.get_or_init(|| {
+            let detected = Capabilities::detect();
+            let theme = Self::load(detected).unwrap_or_else(|message| {
+                eprintln!("Ignoring the theme file, {}", message);
+                Self::new(detected, Palette::default())
+            });
+            colored::control::set_override(theme.colored());
+            theme
+        })
+    }
+
+    fn load(detected: Capabilities) -> Result<Self, String> {
+        let path = std::env::var_os(Self::CONFIG_VARIABLE)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(Self::CONFIG_FILE));
+        match std::fs::read_to_string(&path) {
+            Ok(config) => Self::from_config(&config, detected)
+                .map_err(|message| format!("{}: {}", path.display(), message)),
+            Err(_) if std::env::var_os(Self::CONFIG_VARIABLE).is_none() => {
+                Ok(Self::new(detected, Palette::default()))
+            }
+            Err(error) => Err(format!("{}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn from_config(config: &str, detected: Capabilities) -> Result<Self, String> {
+        let config: ThemeConfig = toml::from_str(config).map_err(|error| error.to_string())?;
+        Ok(Self::new(
+            Capabilities {
+                color: config.color.unwrap_or(detected.color),
+                unicode: config.unicode.unwrap_or(detected.unicode),
+            },
+            config.palette,
+        ))
+    }
+
+    pub fn colored(&self) -> bool {
+        self.capabilities.color != ColorDepth::None
+    }
+
+    pub fn unicode(&self) -> bool {
+        self.capabilities.unicode
+    }
+
+    /// Turns a palette entry into something the terminal can actually show.
+    pub fn color(&self, color: ThemeColor) -> Color {
+        match (self.capabilities.color, color.0) {
+            (ColorDepth::Ansi256, Color::TrueColor { r, g, b }) => {
+                Color::AnsiColor(approximate_256(r, g, b))
+            }
+            (ColorDepth::Ansi16 | ColorDepth::None, Color::TrueColor { r, g, b }) => {
+                approximate_16(r, g, b)
+            }
+            (_, color) => color,
+        }
+    }
+
+    pub fn paint(&self, text: &str, foreground: ThemeColor) -> ColoredString {
+        let text = self.glyphs(text).into_owned();
+        if self.colored() {
+            text.color(self.color(foreground))
+        } else {
+            text.normal()
+        }
+    }
+
+    pub fn paint_on(
+        &self,
+        text: &str,
+        foreground: ThemeColor,
+        background: ThemeColor,
+    ) -> ColoredString {
+        if self.colored() {
+            self.paint(text, foreground)
+                .on_color(self.color(background))
+        } else {
+            self.paint(text, foreground)
+        }
+    }
+
+    /// `paint` in bold, which like the colors is left out when the terminal shows none.
+    pub fn paint_bold(&self, text: &str, foreground: ThemeColor) -> ColoredString {
+        if self.colored() {
+            self.paint(text, foreground).bold()
+        } else {
+            self.paint(text, foreground)
+        }
+    }
+
+    /// Mixes two palette entries, `ratio` 0.0 being `from` and 1.0 being `to`.
+    pub fn gradient(&self, from: ThemeColor, to: ThemeColor, ratio: f32) -> ThemeColor {
+        let (from, to) = (to_rgb(from.0), to_rgb(to.0));
+        let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * ratio.clamp(0.0, 1.0)) as u8;
+        rgb(mix(from.0, to.0), mix(from.1, to.1), mix(from.2, to.2))
+    }
+
+    /// Replaces box drawing, block and sextant glyphs by ASCII look-alikes when the terminal
+    /// cannot be trusted to render them.
+    pub fn glyphs<'a>(&self, text: &'a str) -> std::borrow::Cow<'a, str> {
+        if self.unicode() || text.is_ascii() {
+            text.into()
+        } else {
+            text.chars().map(ascii_fallback).collect::<String>().into()
+        }
+    }
+}
+
+fn ascii_fallback(character: char) -> char {
+    match character {
+        '─' | '┄' => '-',
+        '━' | '┅' => '=',
+        '│' | '┆' => '|',
+        '┃' | '┇' => '#',
+        '╭' | '╮' | '╰' | '╯' | '┏' | '┓' | '┗' | '┛' | '┣' | '┫' | '┼' | '╋' => {
+            '+'
+        }
+        '▁'..='▃' => '_',
+        '▄'..='▇' => '=',
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28FF}' => '*',
+        '░' => '.',
+        '▒' => ':',
+        '▓' => '%',
+        '█' => '#',
+        '≠' => '!',
+        '²' => '2',
+        '\u{1FB00}'..='\u{1FBFF}' => '*',
+        character if character.is_ascii() => character,
+        _ => '?',
+    }
+}
+
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::White, (229, 229, 229)),
+    (Color::BrightBlack, (127, 127, 127)),
+    (Color::BrightRed, (255, 0, 0)),
+    (Color::BrightGreen, (0, 255, 0)),
+    (Color::BrightYellow, (255, 255, 0)),
+    (Color::BrightBlue, (92, 92, 255)),
+    (Color::BrightMagenta, (255, 0, 255)),
+    (Color::BrightCyan, (0, 255, 255)),
+    (Color::BrightWhite, (255, 255, 255)),
+];
+
+fn to_rgb(color: Color) -> (u8, u8, u8) {
+    match color {
+        Color::TrueColor { r, g, b } => (r, g, b),
+        color => BASIC_COLORS
+            .iter()
+            .find(|(basic, _)| *basic == color)
+            .map(|(_, rgb)| *rgb)
+            .unwrap_or((0, 0, 0)),
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let delta = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    delta(a.0, b.0) + delta(a.1, b.1) + delta(a.2, b.2)
+}
+
+fn approximate_16(r: u8, g: u8, b: u8) -> Color {
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, basic)| distance(*basic, (r, g, b)))
+        .map(|(color, _)| *color)
+        .unwrap()
+}
+
+fn approximate_256(r: u8, g: u8, b: u8) -> u8 {
+    // The xterm palette holds a 6x6x6 color cube (16..=231) and a 24 step grey ramp (232..=255)
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |value: u8| {
+        (0..6)
+            .min_by_key(|&index| (LEVELS[index] as i32 - value as i32).abs())
+            .unwrap()
+    };
+    let (r6, g6, b6) = (level(r), level(g), level(b));
+    let cube = (LEVELS[r6], LEVELS[g6], LEVELS[b6]);
+
+    let average = (r as u32 + g as u32 + b as u32) / 3;
+    let grey_index = (average.saturating_sub(3) / 10).min(23) as u8;
+    let grey_value = 8 + 10 * grey_index;
+
+    if distance((grey_value, grey_value, grey_value), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + grey_index
+    } else {
+        (16 + 36 * r6 + 6 * g6 + b6) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::theme::{approximate_256, Capabilities, ColorDepth, Theme, ThemeColor};
+    use colored::{Color, Colorize};
+    use std::collections::HashMap;
+
+    fn detect(vars: &[(&str, &str)], is_terminal: bool) -> Capabilities {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect();
+        Capabilities::from_env(|key| vars.get(key).cloned(), is_terminal)
+    }
+
+    #[test]
+    fn test_color_detection() {
+        let truecolor = [("COLORTERM", "truecolor"), ("TERM", "xterm-256color")];
+        assert_eq!(ColorDepth::TrueColor, detect(&truecolor, true).color);
+        assert_eq!(ColorDepth::None, detect(&truecolor, false).color);
+        assert_eq!(
+            ColorDepth::Ansi256,
+            detect(&[("TERM", "xterm-256color")], true).color
+        );
+        assert_eq!(ColorDepth::Ansi16, detect(&[("TERM", "xterm")], true).color);
+        assert_eq!(
+            ColorDepth::None,
+            detect(&[("NO_COLOR", "1"), ("CLICOLOR_FORCE", "1")], true).color
+        );
+        assert_eq!(
+            ColorDepth::Ansi16,
+            detect(&[("CLICOLOR_FORCE", "1")], false).color
+        );
+    }
+
+    #[test]
+    fn test_unicode_detection() {
+        assert!(detect(&[("LANG", "en_US.UTF-8")], true).unicode);
+        assert!(!detect(&[("LC_ALL", "C"), ("LANG", "en_US.UTF-8")], true).unicode);
+        assert!(!detect(&[], true).unicode);
+    }
+
+    #[test]
+    fn test_approximation() {
+        assert_eq!(196, approximate_256(255, 0, 0));
+        assert_eq!(16, approximate_256(0, 0, 0));
+        assert_eq!(244, approximate_256(128, 128, 128));
+
+        let ansi16 = Capabilities {
+            color: ColorDepth::Ansi16,
+            unicode: false,
+        };
+        let theme = Theme::from_config("", ansi16).unwrap();
+        let green = ThemeColor(Color::TrueColor {
+            r: 10,
+            g: 250,
+            b: 10,
+        });
+        assert_eq!(Color::BrightGreen, theme.color(green));
+        assert_eq!("+--+", theme.glyphs("╭──╮"));
+        assert_eq!("#0afa0a", green.hex());
+        assert_eq!("#cd0000", ThemeColor(Color::Red).hex());
+
+        let plain = Theme::plain();
+        assert_eq!("win".normal(), plain.paint_bold("win", green));
+        assert_eq!("win".bright_green().bold(), theme.paint_bold("win", green));
+    }
+
+    #[test]
+    fn test_config() {
+        let detected = Capabilities {
+            color: ColorDepth::TrueColor,
+            unicode: true,
+        };
+        let theme = Theme::from_config(
+            "color = \"256\"\n[palette]\nerror = \"#ff0000\"\nok = \"bright cyan\"",
+            detected,
+        )
+        .unwrap();
+        assert_eq!(ColorDepth::Ansi256, theme.capabilities.color);
+        assert!(theme.unicode());
+        assert_eq!(Color::AnsiColor(196), theme.color(theme.palette.error));
+        assert_eq!(Color::BrightCyan, theme.color(theme.palette.ok));
+        assert!(Theme::from_config("[palette]\nerror = \"reddish\"", detected).is_err());
+    }
+}
@@ -0,0 +1,55 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "advent_of_code.h"
+
+static int failures = 0;
+
+static void expect(const char *name, AocStatus expected_status, const char *expected,
+                   AocStatus status, const char *answer) {
+    if (status != expected_status || (expected != NULL && strcmp(answer, expected) != 0)) {
+        fprintf(stderr, "%s: expected %d (%s), got %d (%s): %s\n", name, expected_status,
+                aoc_status_message(expected_status), status, aoc_status_message(status), answer);
+        failures++;
+    }
+}
+
+int main(void) {
+    const char *depths = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+    const char *crabs = "16,1,2,0,4,2,7,1,2,14";
+    char data[64];
+    AocBuffer buffer = {data, sizeof(data), 0};
+    AocStatus status;
+
+    status = aoc_solve(2021, 1, 1, (const uint8_t *) depths, strlen(depths), &buffer);
+    expect("sonar sweep", AOC_STATUS_OK, "7", status, data);
+
+    status = aoc_solve(2021, 7, 2, (const uint8_t *) crabs, strlen(crabs), &buffer);
+    expect("whales", AOC_STATUS_OK, "168", status, data);
+
+    status = aoc_solve(2021, 26, 1, (const uint8_t *) crabs, strlen(crabs), &buffer);
+    expect("unknown day", AOC_STATUS_UNKNOWN_PUZZLE, NULL, status, data);
+
+    status = aoc_solve(2021, 1, 1, (const uint8_t *) "-1.5\n-0.25\n0.5\n", 15, &buffer);
+    expect("fractional depths", AOC_STATUS_OK, "2", status, data);
+
+    status = aoc_solve(2021, 1, 1, (const uint8_t *) "12\nfish\n", 8, &buffer);
+    expect("invalid input", AOC_STATUS_INVALID_INPUT,
+           "Invalid input: Line 2 is not a depth: fish", status, data);
+
+    status = aoc_solve(2021, 1, 1, (const uint8_t *) "\xff\xfe", 2, &buffer);
+    expect("invalid utf-8", AOC_STATUS_INVALID_UTF8, NULL, status, data);
+
+    AocBuffer tiny = {data, 1, 0};
+    status = aoc_solve(2021, 7, 2, (const uint8_t *) crabs, strlen(crabs), &tiny);
+    expect("tiny buffer", AOC_STATUS_BUFFER_TOO_SMALL, "", status, data);
+    if (tiny.length != 3) {
+        fprintf(stderr, "tiny buffer: expected a required length of 3, got %zu\n", tiny.length);
+        failures++;
+    }
+
+    status = aoc_solve(2021, 1, 1, NULL, 4, &buffer);
+    expect("null input", AOC_STATUS_NULL_POINTER, NULL, status, data);
+
+    return failures;
+}
@@ -0,0 +1,40 @@
+#![cfg(unix)]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Integration tests are built into target/<profile>/deps, right next to the cdylib
+fn deps_dir() -> PathBuf {
+    let executable = std::env::current_exe().unwrap();
+    executable.parent().unwrap().to_path_buf()
+}
+
+#[test]
+fn test_c_harness() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let deps_dir = deps_dir();
+    let harness = deps_dir.join("c_harness");
+
+    let compiled = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(root.join("tests/c/harness.c"))
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I")
+        .arg(root.join("include"))
+        .arg("-L")
+        .arg(&deps_dir)
+        .arg(format!("-Wl,-rpath,{}", deps_dir.display()))
+        .arg("-ladvent_of_code")
+        .arg("-o")
+        .arg(&harness)
+        .status()
+        .expect("A C compiler is needed to test the C ABI");
+    assert!(compiled.success());
+
+    let output = Command::new(&harness).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}